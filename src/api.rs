@@ -0,0 +1,710 @@
+//! Public API surface of a package entrypoint, and diffs between two
+//! surface snapshots.
+//!
+//! The surface is every name the entry exports, with `export *` flattened and
+//! re-exports followed to their declarations. Class, interface, enum and
+//! object-type members are listed separately so a diff can tell an added
+//! optional member from a removed one. Breaking-change classification is a
+//! syntactic heuristic over the skeleton signatures.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::graph::AppState;
+use crate::skeleton::SymbolInfo;
+use crate::symbols::{resolve_export, ExportResolution};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiSurface {
+    pub entry: String,
+    pub exports: Vec<ApiExport>,
+    /// `export * from` specifiers that leave the graph (packages) and so
+    /// cannot be flattened.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_stars: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiExport {
+    /// Name as seen by importers of the entry.
+    pub name: String,
+    /// A symbol kind, or namespace | external | unresolved.
+    pub kind: String,
+    /// Declaration without bodies; for members-bearing kinds, the header
+    /// only (`class Repo<T> extends Base`). Overloads are one per line.
+    pub signature: String,
+    /// Declaring graph key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<ApiMember>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiMember {
+    pub name: String,
+    pub signature: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiChange {
+    pub name: String,
+    /// added | removed | kind_changed | signature_changed
+    pub change: &'static str,
+    pub breaking: bool,
+    /// Adds to the surface (a new export, optional parameter, overload or
+    /// member); without a breaking change this is a minor bump.
+    pub additive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    /// Member- and parameter-level reasons behind the classification.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ApiDiff {
+    /// major | minor | patch: the smallest version bump the changes allow.
+    pub semver: &'static str,
+    /// Breaking changes first, then by name.
+    pub changes: Vec<ApiChange>,
+}
+
+/// Every name `key` exports, with `export *` expanded (it never forwards
+/// `default`, and explicit exports shadow it).
+fn export_names(
+    state: &AppState,
+    key: &str,
+    visited: &mut HashSet<String>,
+    names: &mut BTreeSet<String>,
+    stars: &mut Vec<String>,
+) {
+    if !visited.insert(key.to_string()) {
+        return;
+    }
+    let Some(node) = state.skeleton_graph.get(key).map(|n| n.clone()) else {
+        return;
+    };
+    for entry in node.export_table.iter().filter(|e| e.name != "*") {
+        names.insert(entry.name.clone());
+    }
+    for entry in node.export_table.iter().filter(|e| e.name == "*") {
+        let Some(source) = &entry.source else {
+            continue;
+        };
+        let target = node
+            .import_records
+            .iter()
+            .find(|r| &r.source == source)
+            .and_then(|r| r.resolved.clone());
+        match target {
+            Some(target) => {
+                let mut forwarded = BTreeSet::new();
+                export_names(state, &target, visited, &mut forwarded, stars);
+                forwarded.remove("default");
+                names.extend(forwarded);
+            }
+            None => stars.push(format!("{} (from {})", source, key)),
+        }
+    }
+}
+
+/// Scans `text` outside strings and brackets. `at_depth_zero` sees each
+/// top-level character with its byte offset; angle brackets count as
+/// brackets except in `=>`.
+fn scan_top_level(text: &str, mut at_depth_zero: impl FnMut(usize, char) -> bool) {
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut prev = '\0';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) => {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => quote = Some(c),
+                '(' | '[' | '{' | '<' => {
+                    if depth == 0 && at_depth_zero(i, c) {
+                        return;
+                    }
+                    depth += 1;
+                }
+                '>' if prev == '=' => {}
+                ')' | ']' | '}' | '>' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 && at_depth_zero(i, c) {
+                        return;
+                    }
+                }
+                _ => {
+                    if depth == 0 && at_depth_zero(i, c) {
+                        return;
+                    }
+                }
+            },
+        }
+        prev = c;
+    }
+}
+
+/// Splits on `sep` at the top level. With `sep == ';'`, a `}` returning to
+/// the top level (a stripped method body or a type literal) also ends a
+/// segment.
+fn split_top_level(text: &str, sep: char) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    scan_top_level(text, |i, c| {
+        if c == sep {
+            out.push(text[start..i].trim().to_string());
+            start = i + c.len_utf8();
+        } else if sep == ';' && c == '}' {
+            out.push(text[start..=i].trim().to_string());
+            start = i + 1;
+        }
+        false
+    });
+    out.push(text[start..].trim().to_string());
+    out.retain(|s| !s.is_empty());
+    out
+}
+
+/// `(header, body)` of a declaration whose outermost braces close at the end
+/// of the signature: `class X { ... }`, `interface I { ... }`,
+/// `type T = { ... }`, `enum E { ... }`.
+fn braced(signature: &str) -> Option<(&str, &str)> {
+    let signature = signature.trim().trim_end_matches(';');
+    let mut open = None;
+    scan_top_level(signature, |i, c| {
+        if c == '{' {
+            open = Some(i);
+            return true;
+        }
+        false
+    });
+    let open = open?;
+    let mut close = None;
+    scan_top_level(&signature[open..], |i, c| {
+        if c == '}' {
+            close = Some(open + i);
+            return true;
+        }
+        false
+    });
+    (close? == signature.len() - 1)
+        .then(|| (signature[..open].trim(), &signature[open + 1..signature.len() - 1]))
+}
+
+const MEMBER_MODIFIERS: &[&str] = &[
+    "public", "protected", "static", "readonly", "async", "abstract", "override", "declare",
+    "accessor",
+];
+
+/// Identity of a member across versions: its name, with `get `/`set `/
+/// `static ` kept so accessors and statics don't collide.
+fn member_key(text: &str) -> String {
+    let mut rest = text.trim();
+    let mut prefix = String::new();
+    while let Some(word) = MEMBER_MODIFIERS
+        .iter()
+        .chain(&["get", "set"])
+        .find(|m| rest.strip_prefix(**m).is_some_and(|r| r.starts_with(' ')))
+    {
+        if matches!(*word, "static" | "get" | "set") {
+            prefix.push_str(word);
+            prefix.push(' ');
+        }
+        rest = rest[word.len()..].trim_start();
+    }
+    let name = if rest.starts_with('[') {
+        let end = rest.find(']').map_or(rest.len(), |i| i + 1);
+        &rest[..end]
+    } else {
+        let end = rest.find(['(', ':', '=', '?', '!', '<', ';']).unwrap_or(rest.len());
+        rest[..end].trim()
+    };
+    format!("{}{}", prefix, name)
+}
+
+/// Drops the stripped body (`{}`, or `=> {}` for arrows) and a trailing `;`.
+fn strip_body(signature: &str) -> &str {
+    let s = signature.trim().trim_end_matches(';').trim_end_matches("{}").trim_end();
+    s.strip_suffix("=>").map_or(s, str::trim_end)
+}
+
+/// Public members of a class, interface, enum or object type.
+fn members(kind: &str, body: &str) -> Vec<ApiMember> {
+    let sep = if kind == "enum" { ',' } else { ';' };
+    let parts = split_top_level(body, sep);
+    let mut by_key: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for part in &parts {
+        if part.starts_with("private ") || part.starts_with('#') {
+            continue;
+        }
+        by_key.entry(member_key(part)).or_default().push(part);
+    }
+    by_key
+        .into_iter()
+        .map(|(name, mut texts)| {
+            // Overloaded methods: the implementation signature isn't public.
+            if texts.len() > 1 {
+                texts.retain(|t| !t.ends_with("{}"));
+            }
+            ApiMember {
+                name,
+                signature: texts.iter().map(|t| strip_body(t)).collect::<Vec<_>>().join("\n"),
+            }
+        })
+        .collect()
+}
+
+fn declared(name: &str, file: String, symbol: &SymbolInfo) -> ApiExport {
+    let kind = symbol.kind.clone();
+    let public_overloads: Vec<&str> = symbol
+        .overloads
+        .iter()
+        .filter(|o| !o.implementation)
+        .map(|o| strip_body(&o.signature))
+        .collect();
+    let full = if public_overloads.is_empty() {
+        strip_body(&symbol.signature).to_string()
+    } else {
+        public_overloads.join("\n")
+    };
+    let (signature, members) = match braced(&full) {
+        Some((header, body)) if matches!(kind.as_str(), "class" | "interface" | "enum" | "type") => {
+            (header.to_string(), members(&kind, body))
+        }
+        _ => (full, Vec::new()),
+    };
+    ApiExport {
+        name: name.to_string(),
+        kind,
+        signature,
+        file: Some(file),
+        members,
+    }
+}
+
+/// The flattened public surface of `entry`, sorted by export name.
+pub fn surface(state: &AppState, entry: &str) -> ApiSurface {
+    surface_in(state, entry, &mut HashSet::new())
+}
+
+/// `surface` with the entries whose namespaces are being expanded; a
+/// namespace re-export back into one of them (`a.ts: export * as b from
+/// './b'`, `b.ts: export * as a from './a'`) is listed by name only.
+fn surface_in(state: &AppState, entry: &str, expanding: &mut HashSet<String>) -> ApiSurface {
+    expanding.insert(entry.to_string());
+    let mut names = BTreeSet::new();
+    let mut unresolved_stars = Vec::new();
+    export_names(state, entry, &mut HashSet::new(), &mut names, &mut unresolved_stars);
+    let exports = names
+        .into_iter()
+        .map(|name| match resolve_export(state, entry, &name) {
+            ExportResolution::Defined(def) => declared(&name, def.file, &def.symbol),
+            ExportResolution::Namespace { file, .. } => {
+                let members = if expanding.contains(&file) {
+                    Vec::new()
+                } else {
+                    surface_in(state, &file, expanding)
+                        .exports
+                        .into_iter()
+                        .map(|e| ApiMember {
+                            name: e.name,
+                            signature: e.signature,
+                        })
+                        .collect()
+                };
+                ApiExport {
+                    signature: format!("export * as {} from {}", name, file),
+                    name,
+                    kind: "namespace".to_string(),
+                    file: Some(file),
+                    members,
+                }
+            }
+            ExportResolution::External { package, name: imported, .. } => ApiExport {
+                signature: format!("export {{ {} }} from \"{}\"", imported, package),
+                name,
+                kind: "external".to_string(),
+                file: None,
+                members: Vec::new(),
+            },
+            other => ApiExport {
+                signature: other.failure(&name).unwrap_or_default(),
+                name,
+                kind: "unresolved".to_string(),
+                file: None,
+                members: Vec::new(),
+            },
+        })
+        .collect();
+    expanding.remove(entry);
+    ApiSurface {
+        entry: entry.to_string(),
+        exports,
+        unresolved_stars,
+    }
+}
+
+fn render(e: &ApiExport) -> String {
+    if e.members.is_empty() {
+        e.signature.clone()
+    } else {
+        let members: Vec<&str> = e.members.iter().map(|m| m.signature.as_str()).collect();
+        format!("{} {{ {} }}", e.signature, members.join("; "))
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parameter list and the text after it (return type) of a callable
+/// signature.
+fn parameters(signature: &str) -> Option<(Vec<String>, String)> {
+    let mut open = None;
+    scan_top_level(signature, |i, c| {
+        if c == '(' {
+            open = Some(i);
+            return true;
+        }
+        false
+    });
+    let open = open?;
+    let mut close = None;
+    scan_top_level(&signature[open..], |i, c| {
+        if c == ')' {
+            close = Some(open + i);
+            return true;
+        }
+        false
+    });
+    let close = close?;
+    let params = split_top_level(&signature[open + 1..close], ',')
+        .iter()
+        .map(|p| normalize(p))
+        .collect();
+    Some((params, normalize(strip_body(&signature[close + 1..]))))
+}
+
+/// Rest, `name?` and defaulted parameters. Only a top-level `=` that is
+/// not part of `=>` is a default initializer; a function-typed parameter
+/// is still required.
+fn optional_param(param: &str) -> bool {
+    let name_end = param.find([':', '=']).unwrap_or(param.len());
+    let mut has_default = false;
+    scan_top_level(param, |i, c| {
+        has_default = c == '=' && !param[i + 1..].starts_with('>');
+        has_default
+    });
+    param.starts_with("...") || param[..name_end].trim_end().ends_with('?') || has_default
+}
+
+/// Reasons behind one export's classification.
+#[derive(Default)]
+struct Findings {
+    details: Vec<String>,
+    breaking: bool,
+    additive: bool,
+}
+
+impl Findings {
+    fn breaking(&mut self, detail: String) {
+        self.details.push(detail);
+        self.breaking = true;
+    }
+
+    fn additive(&mut self, detail: String) {
+        self.details.push(detail);
+        self.additive = true;
+    }
+}
+
+/// A callable's change; only appending optional parameters is compatible.
+fn callable_change(before: &str, after: &str, found: &mut Findings) {
+    let (Some((old, old_ret)), Some((new, new_ret))) = (parameters(before), parameters(after)) else {
+        found.breaking("signature changed".to_string());
+        return;
+    };
+    if new.len() < old.len() {
+        found.breaking(format!("parameters reduced from {} to {}", old.len(), new.len()));
+    }
+    for (i, (o, n)) in old.iter().zip(&new).enumerate() {
+        if o != n {
+            found.breaking(format!("parameter {} changed: '{}' -> '{}'", i + 1, o, n));
+        }
+    }
+    for n in new.iter().skip(old.len()) {
+        if optional_param(n) {
+            found.additive(format!("optional parameter added: '{}'", n));
+        } else {
+            found.breaking(format!("required parameter added: '{}'", n));
+        }
+    }
+    if old_ret != new_ret {
+        found.breaking(format!("return type changed: '{}' -> '{}'", old_ret, new_ret));
+    }
+}
+
+/// Overload sets: dropping a signature breaks callers, adding one doesn't.
+fn overload_changes(before: &str, after: &str, found: &mut Findings) {
+    let old: BTreeSet<String> = before.lines().map(normalize).collect();
+    let new: BTreeSet<String> = after.lines().map(normalize).collect();
+    for removed in old.difference(&new) {
+        found.breaking(format!("overload removed: '{}'", removed));
+    }
+    for added in new.difference(&old) {
+        found.additive(format!("overload added: '{}'", added));
+    }
+}
+
+/// Member-level comparison.
+fn member_changes(kind: &str, before: &ApiExport, after: &ApiExport, found: &mut Findings) {
+    if normalize(&before.signature) != normalize(&after.signature) {
+        found.breaking(format!("declaration changed: '{}' -> '{}'", before.signature, after.signature));
+    }
+    let old: BTreeMap<&str, &str> = before.members.iter().map(|m| (m.name.as_str(), m.signature.as_str())).collect();
+    let new: BTreeMap<&str, &str> = after.members.iter().map(|m| (m.name.as_str(), m.signature.as_str())).collect();
+    for (name, sig) in &old {
+        match new.get(name) {
+            None => found.breaking(format!("member removed: '{}'", name)),
+            Some(n) if normalize(sig) != normalize(n) => {
+                found.breaking(format!("member changed: '{}' -> '{}'", sig, n));
+            }
+            _ => {}
+        }
+    }
+    for (name, sig) in &new {
+        if old.contains_key(name) {
+            continue;
+        }
+        // Implementers of an interface or object type must provide every
+        // required member.
+        let optional = sig
+            .find(name)
+            .is_some_and(|i| sig[i + name.len()..].trim_start().starts_with('?'));
+        let required = matches!(kind, "interface" | "type") && !optional;
+        if required {
+            found.breaking(format!("required member added: '{}'", name));
+        } else {
+            found.additive(format!("member added: '{}'", name));
+        }
+    }
+}
+
+const CALLABLE: &[&str] = &["function", "arrow_function", "component"];
+
+fn compare(before: &ApiExport, after: &ApiExport) -> Option<ApiChange> {
+    let (old, new) = (render(before), render(after));
+    if before.kind == after.kind && normalize(&old) == normalize(&new) {
+        return None;
+    }
+    let both_callable = CALLABLE.contains(&before.kind.as_str()) && CALLABLE.contains(&after.kind.as_str());
+    let mut found = Findings::default();
+    let change = if before.kind != after.kind && !both_callable {
+        found.breaking(format!("kind changed: {} -> {}", before.kind, after.kind));
+        "kind_changed"
+    } else if both_callable && !before.signature.contains('\n') && !after.signature.contains('\n') {
+        callable_change(&before.signature, &after.signature, &mut found);
+        "signature_changed"
+    } else if both_callable {
+        overload_changes(&before.signature, &after.signature, &mut found);
+        "signature_changed"
+    } else if !before.members.is_empty() || !after.members.is_empty() {
+        member_changes(&after.kind, before, after, &mut found);
+        "signature_changed"
+    } else {
+        found.breaking("signature changed".to_string());
+        "signature_changed"
+    };
+    Some(ApiChange {
+        name: after.name.clone(),
+        change,
+        breaking: found.breaking,
+        additive: found.additive,
+        before: Some(old),
+        after: Some(new),
+        details: found.details,
+    })
+}
+
+/// Changes from `before` to `after`, with the semver bump they imply.
+pub fn diff(before: &ApiSurface, after: &ApiSurface) -> ApiDiff {
+    let old: BTreeMap<&str, &ApiExport> = before.exports.iter().map(|e| (e.name.as_str(), e)).collect();
+    let new: BTreeMap<&str, &ApiExport> = after.exports.iter().map(|e| (e.name.as_str(), e)).collect();
+    let mut changes = Vec::new();
+    for (name, e) in &old {
+        match new.get(name) {
+            None => changes.push(ApiChange {
+                name: name.to_string(),
+                change: "removed",
+                breaking: true,
+                additive: false,
+                before: Some(render(e)),
+                after: None,
+                details: Vec::new(),
+            }),
+            Some(n) => changes.extend(compare(e, n)),
+        }
+    }
+    for (name, e) in &new {
+        if !old.contains_key(name) {
+            changes.push(ApiChange {
+                name: name.to_string(),
+                change: "added",
+                breaking: false,
+                additive: true,
+                before: None,
+                after: Some(render(e)),
+                details: Vec::new(),
+            });
+        }
+    }
+    changes.sort_by(|a, b| (!a.breaking, &a.name).cmp(&(!b.breaking, &b.name)));
+    let semver = if changes.iter().any(|c| c.breaking) {
+        "major"
+    } else if changes.iter().any(|c| c.additive) {
+        "minor"
+    } else {
+        "patch"
+    };
+    ApiDiff { semver, changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn surface_flattens_star_and_renamed_reexports() {
+        let (_dir, state) = project(&[
+            ("src/index.ts", "export * from './client';\nexport * as util from './util';\nexport { helper as assist } from './util';\nexport * from 'react';\n"),
+            (
+                "src/client.ts",
+                "export class Client extends Base {\n  private token = '';\n  #secret = 1;\n  readonly url: string;\n  get(id: string): Item;\n  get(id: number): Item;\n  get(id: any) { return id; }\n}\nexport interface Options { retries?: number; timeout: number }\nexport function connect(url: string, opts?: Options): Client { return new Client(); }\nexport default 1;\n",
+            ),
+            ("src/util.ts", "export const helper = (x: number) => x;\nexport enum Level { Low, High = 3 }\n"),
+        ]);
+        let surface = surface(&state, "src/index.ts");
+        let names: Vec<&str> = surface.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Client", "Options", "assist", "connect", "util"], "export * drops default");
+        assert_eq!(surface.unresolved_stars, ["react (from src/index.ts)"]);
+
+        let client = &surface.exports[0];
+        assert_eq!(client.signature, "class Client extends Base");
+        assert_eq!(client.file.as_deref(), Some("src/client.ts"));
+        let members: Vec<(&str, &str)> = client
+            .members
+            .iter()
+            .map(|m| (m.name.as_str(), m.signature.as_str()))
+            .collect();
+        assert_eq!(
+            members,
+            [("get", "get(id: string): Item\nget(id: number): Item"), ("url", "readonly url: string")]
+        );
+        assert_eq!(surface.exports[1].members.len(), 2);
+        assert_eq!(surface.exports[2].signature, "const helper = (x: number)");
+        assert_eq!(surface.exports[3].signature, "function connect(url: string, opts?: Options): Client");
+        let util = &surface.exports[4];
+        assert_eq!(util.kind, "namespace");
+        assert_eq!(util.members.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), ["Level", "helper"]);
+    }
+
+    #[test]
+    fn mutually_namespaced_files_expand_once() {
+        let (_dir, state) = project(&[
+            ("src/a.ts", "export * as b from './b';\nexport const x = 1;\n"),
+            ("src/b.ts", "export * as a from './a';\nexport const y = 2;\n"),
+        ]);
+        let surface = surface(&state, "src/a.ts");
+        let b = &surface.exports[0];
+        let members: Vec<&str> = b.members.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(members, ["a", "y"]);
+        assert_eq!(b.members[0].signature, "export * as a from src/a.ts");
+    }
+
+    fn export(name: &str, kind: &str, signature: &str, members: &[(&str, &str)]) -> ApiExport {
+        ApiExport {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            signature: signature.to_string(),
+            file: None,
+            members: members
+                .iter()
+                .map(|(n, s)| ApiMember {
+                    name: n.to_string(),
+                    signature: s.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn snapshot(exports: Vec<ApiExport>) -> ApiSurface {
+        ApiSurface {
+            entry: "src/index.ts".to_string(),
+            exports,
+            unresolved_stars: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn diff_classifies_breaking_and_compatible_changes() {
+        let before = snapshot(vec![
+            export("connect", "function", "function connect(url: string): Client", &[]),
+            export("parse", "function", "function parse(s: string, strict: boolean): Ast", &[]),
+            export("Options", "interface", "interface Options", &[("timeout", "timeout: number")]),
+            export("Config", "interface", "interface Config", &[("a", "a: string")]),
+            export("legacy", "variable", "const legacy = 1", &[]),
+            export("Mode", "enum", "enum Mode", &[("A", "A")]),
+            export("on", "function", "function on(a: string): void", &[]),
+            export("listen", "function", "function listen(port: number): void", &[]),
+        ]);
+        let after = snapshot(vec![
+            export("connect", "function", "function connect(url: string, opts?: Options): Client", &[]),
+            export("parse", "arrow_function", "const parse = (s: string, strict: number): Ast", &[]),
+            export("Options", "interface", "interface Options", &[("retries", "retries?: number"), ("timeout", "timeout: number")]),
+            export("Config", "interface", "interface Config", &[("a", "a: string"), ("b", "b: string")]),
+            export("Mode", "class", "class Mode", &[]),
+            export("fresh", "function", "function fresh(): void", &[]),
+            export("on", "function", "function on(a: string, cb: (x: number) => void): void", &[]),
+            export("listen", "function", "function listen(port: number, host = \"::\"): void", &[]),
+        ]);
+        let diff = diff(&before, &after);
+        assert_eq!(diff.semver, "major");
+        let got: Vec<(&str, &str, bool)> = diff.changes.iter().map(|c| (c.name.as_str(), c.change, c.breaking)).collect();
+        assert_eq!(
+            got,
+            [
+                ("Config", "signature_changed", true),
+                ("Mode", "kind_changed", true),
+                ("legacy", "removed", true),
+                ("on", "signature_changed", true),
+                ("parse", "signature_changed", true),
+                ("Options", "signature_changed", false),
+                ("connect", "signature_changed", false),
+                ("fresh", "added", false),
+                ("listen", "signature_changed", false),
+            ]
+        );
+        let parse = diff.changes.iter().find(|c| c.name == "parse").unwrap();
+        assert_eq!(parse.details, ["parameter 2 changed: 'strict: boolean' -> 'strict: number'"]);
+        let config = diff.changes.iter().find(|c| c.name == "Config").unwrap();
+        assert_eq!(config.details, ["required member added: 'b'"]);
+        let connect = diff.changes.iter().find(|c| c.name == "connect").unwrap();
+        assert!(connect.additive && !connect.breaking);
+        let on = diff.changes.iter().find(|c| c.name == "on").unwrap();
+        assert_eq!(on.details, ["required parameter added: 'cb: (x: number) => void'"]);
+
+        let connect = |e: ApiExport| snapshot(vec![e]);
+        let old = connect(before.exports[0].clone());
+        let new = connect(after.exports[0].clone());
+        assert_eq!(super::diff(&old, &new).semver, "minor", "an optional parameter is additive");
+        assert_eq!(super::diff(&new, &new).semver, "patch");
+    }
+}
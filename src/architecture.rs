@@ -0,0 +1,312 @@
+//! Declarative architecture rules from `.skeletonizer.json`, evaluated
+//! against each file's resolved import records.
+//!
+//! ```json
+//! { "architecture": { "rules": [
+//!     { "name": "ui-not-db", "from": ["src/ui/"], "disallow": ["src/db/"] },
+//!     { "name": "api-facade", "module": "src/api/", "public": ["src/api/index.ts"] },
+//!     { "name": "no-server-packages-in-ui", "from": ["src/ui/"], "disallow_packages": ["pg"] }
+//! ] } }
+//! ```
+//!
+//! Globs are gitignore-style, like every other glob argument. Violations are
+//! kept per importing file and refreshed from each watcher `ChangeSet`; only
+//! a file's own imports decide its violations, so that is all that needs
+//! re-checking.
+
+use anyhow::{bail, Context, Result};
+use ignore::gitignore::Gitignore;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::graph::{glob_matcher, key_matches, AppState};
+use crate::skeleton::{FileSkeleton, Location};
+use crate::watcher::ChangeSet;
+
+pub const CONFIG_FILE: &str = ".skeletonizer.json";
+
+#[derive(Deserialize, Debug, Default)]
+struct ConfigFile {
+    #[serde(default)]
+    architecture: ArchitectureConfig,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct ArchitectureConfig {
+    #[serde(default)]
+    rules: Vec<RuleConfig>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default, deny_unknown_fields)]
+struct RuleConfig {
+    name: Option<String>,
+    /// Importers the rule applies to (default: every file).
+    from: Vec<String>,
+    /// Files they may not import...
+    disallow: Vec<String>,
+    /// ...unless also matched here.
+    allow: Vec<String>,
+    /// Packages they may not import (`pg` also covers `pg/lib`).
+    disallow_packages: Vec<String>,
+    /// A module whose files may only be imported from outside through
+    /// `public`.
+    module: Option<String>,
+    public: Vec<String>,
+}
+
+struct Rule {
+    name: String,
+    from: Option<Gitignore>,
+    disallow: Option<Gitignore>,
+    allow: Option<Gitignore>,
+    disallow_packages: Vec<String>,
+    module: Option<(String, Gitignore, Gitignore)>,
+}
+
+/// The loaded rule set; `error` holds a config problem to report instead.
+#[derive(Default)]
+pub struct Architecture {
+    rules: Vec<Rule>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: String,
+    pub message: String,
+    /// The importing file.
+    pub file: String,
+    /// The import specifier as written.
+    pub source: String,
+    /// Graph key or package name it reaches.
+    pub target: String,
+    pub type_only: bool,
+    /// The import statement.
+    pub location: Location,
+}
+
+fn matcher(root: &Path, globs: &[String]) -> Result<Option<Gitignore>> {
+    if globs.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(glob_matcher(root, globs)?))
+}
+
+impl Rule {
+    fn compile(root: &Path, index: usize, config: RuleConfig) -> Result<Rule> {
+        let name = config.name.unwrap_or_else(|| format!("rule {}", index + 1));
+        let module = match config.module {
+            Some(module) => {
+                if config.public.is_empty() {
+                    bail!("{}: module needs public entry globs", name);
+                }
+                let inside = glob_matcher(root, std::slice::from_ref(&module))?;
+                Some((module, inside, glob_matcher(root, &config.public)?))
+            }
+            None => None,
+        };
+        if module.is_none() && config.disallow.is_empty() && config.disallow_packages.is_empty() {
+            bail!("{}: needs disallow, disallow_packages or module", name);
+        }
+        Ok(Rule {
+            from: matcher(root, &config.from).with_context(|| format!("{}: from", name))?,
+            disallow: matcher(root, &config.disallow).with_context(|| format!("{}: disallow", name))?,
+            allow: matcher(root, &config.allow).with_context(|| format!("{}: allow", name))?,
+            disallow_packages: config.disallow_packages,
+            module,
+            name,
+        })
+    }
+
+    fn check(&self, key: &str, node: &FileSkeleton, out: &mut Vec<Violation>) {
+        let applies = self.from.as_ref().is_none_or(|from| key_matches(from, key));
+        for record in &node.import_records {
+            let violation = |target: &str, message: String| Violation {
+                rule: self.name.clone(),
+                message,
+                file: key.to_string(),
+                source: record.source.clone(),
+                target: target.to_string(),
+                type_only: record.type_only,
+                location: record.location,
+            };
+            let Some(target) = &record.resolved else {
+                if applies
+                    && let Some(package) = self.disallow_packages.iter().find(|p| {
+                        record.source == **p || record.source.starts_with(&format!("{}/", p))
+                    })
+                {
+                    out.push(violation(
+                        package,
+                        format!("{} imports package '{}', which '{}' disallows", key, package, self.name),
+                    ));
+                }
+                continue;
+            };
+            if applies
+                && let Some(disallow) = &self.disallow
+                && key_matches(disallow, target)
+                && !self.allow.as_ref().is_some_and(|allow| key_matches(allow, target))
+            {
+                out.push(violation(
+                    target,
+                    format!("{} imports {}, which '{}' disallows", key, target, self.name),
+                ));
+            }
+            if let Some((module, inside, public)) = &self.module
+                && key_matches(inside, target)
+                && !key_matches(inside, key)
+                && !key_matches(public, target)
+            {
+                out.push(violation(
+                    target,
+                    format!(
+                        "{} imports {} from outside {}; '{}' only allows its public entry points",
+                        key, target, module, self.name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+impl Architecture {
+    /// Rules from `CONFIG_FILE` under `root`; none when the file is absent.
+    pub fn load(root: &Path) -> Architecture {
+        let path = root.join(CONFIG_FILE);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Architecture::default(),
+            Err(e) => return Architecture::failed(format!("cannot read {}: {}", CONFIG_FILE, e)),
+        };
+        let parsed = serde_json::from_str::<ConfigFile>(&text)
+            .with_context(|| format!("invalid {}", CONFIG_FILE))
+            .and_then(|config| {
+                config
+                    .architecture
+                    .rules
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, rule)| Rule::compile(root, i, rule))
+                    .collect::<Result<Vec<_>>>()
+            });
+        match parsed {
+            Ok(rules) => Architecture { rules, error: None },
+            Err(e) => Architecture::failed(format!("{:#}", e)),
+        }
+    }
+
+    fn failed(error: String) -> Architecture {
+        tracing::warn!("architecture rules: {}", error);
+        Architecture {
+            rules: Vec::new(),
+            error: Some(error),
+        }
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn check(&self, key: &str, node: &FileSkeleton) -> Vec<Violation> {
+        let mut out = Vec::new();
+        for rule in &self.rules {
+            rule.check(key, node, &mut out);
+        }
+        out
+    }
+}
+
+/// Reload the rules and re-check every file.
+pub fn reload(state: &AppState) {
+    let architecture = Architecture::load(&state.root);
+    state.violations.clear();
+    for entry in state.skeleton_graph.iter() {
+        let found = architecture.check(entry.key(), entry.value());
+        if !found.is_empty() {
+            state.violations.insert(entry.key().clone(), found);
+        }
+    }
+    *state.architecture.write().unwrap() = architecture;
+}
+
+/// Re-check the files a watcher batch touched, or everything when the rules
+/// themselves changed.
+pub fn refresh(state: &AppState, changes: &ChangeSet) {
+    if changes.rules_changed {
+        return reload(state);
+    }
+    let architecture = state.architecture.read().unwrap();
+    for key in &changes.removed {
+        state.violations.remove(key);
+    }
+    for key in changes.updated.iter().chain(&changes.added) {
+        let found = state
+            .skeleton_graph
+            .get(key)
+            .map(|node| architecture.check(key, &node))
+            .unwrap_or_default();
+        if found.is_empty() {
+            state.violations.remove(key);
+        } else {
+            state.violations.insert(key.clone(), found);
+        }
+    }
+}
+
+/// Current violations, by file then position.
+pub fn violations(state: &AppState) -> Vec<Violation> {
+    let mut all: Vec<Violation> = state
+        .violations
+        .iter()
+        .flat_map(|e| e.value().clone())
+        .collect();
+    all.sort_by(|a, b| (&a.file, a.location.start, &a.rule).cmp(&(&b.file, b.location.start, &b.rule)));
+    all
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn rules_flag_disallowed_files_private_module_files_and_packages() {
+        let config = r#"{ "architecture": { "rules": [
+            { "name": "ui-not-db", "from": ["src/ui/"], "disallow": ["src/db/"], "allow": ["src/db/types.ts"] },
+            { "name": "api-facade", "module": "src/api/", "public": ["src/api/index.ts"] },
+            { "from": ["src/ui/"], "disallow_packages": ["pg"] }
+        ] } }"#;
+        let (_dir, state) = project(&[
+            (CONFIG_FILE, config),
+            ("src/db/client.ts", "export const db = 1;\n"),
+            ("src/db/types.ts", "export type Row = {};\n"),
+            ("src/api/index.ts", "import { helper } from './internal';\nexport const api = 1;\n"),
+            ("src/api/internal.ts", "export const helper = 1;\n"),
+            (
+                "src/ui/Page.ts",
+                "import { db } from '../db/client';\nimport type { Row } from '../db/types';\nimport { api } from '../api';\nimport { helper } from '../api/internal';\nimport { Pool } from 'pg/lib';\nimport pgx from 'pgx';\n",
+            ),
+        ]);
+        reload(&state);
+        let found: Vec<(String, String, u32)> = violations(&state)
+            .into_iter()
+            .map(|v| (v.rule, v.target, v.location.start_line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("ui-not-db".to_string(), "src/db/client.ts".to_string(), 1),
+                ("api-facade".to_string(), "src/api/internal.ts".to_string(), 4),
+                ("rule 3".to_string(), "pg".to_string(), 5),
+            ]
+        );
+
+        std::fs::write(state.root.join(CONFIG_FILE), r#"{ "architecture": { "rules": [ { "name": "x" } ] } }"#).unwrap();
+        refresh(&state, &ChangeSet { rules_changed: true, ..ChangeSet::default() });
+        assert!(violations(&state).is_empty());
+        let error = state.architecture.read().unwrap().error.clone().unwrap();
+        assert!(error.contains("x: needs disallow"), "{}", error);
+    }
+}
@@ -0,0 +1,415 @@
+//! Syntactic intra-file call graph.
+//!
+//! Nodes are a file's top-level functions, function/arrow-valued variable
+//! bindings and class methods (named `Class.method`, as in
+//! `get_implementation`). Edges are calls by bare identifier and
+//! `this.method()` inside a class. Nested functions are folded into their
+//! top-level node. Calls of imported bindings are kept separately as
+//! `ImportedCall`s for the cross-file queries in `symbols`. No scope or type
+//! analysis: a local that shadows a top-level name still produces an edge.
+
+use oxc_ast::ast::*;
+use oxc_ast_visit::{walk, Visit};
+use oxc_span::Span;
+use oxc_syntax::scope::ScopeFlags;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::skeleton::ImportRecord;
+
+/// `caller` calls `callee` on each of `lines` (1-based).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    pub caller: String,
+    pub callee: String,
+    pub lines: Vec<u32>,
+}
+
+/// `caller` calls the imported binding `local` (or `local.member` when
+/// `local` is a namespace import) on each of `lines`. Resolved to a target
+/// file and symbol at query time, through the import's graph edge.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImportedCall {
+    pub caller: String,
+    pub local: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member: Option<String>,
+    pub lines: Vec<u32>,
+}
+
+/// Collects candidate call targets inside one node's body.
+struct CallCollector<'c> {
+    /// Class whose members `this.x()` refers to; `None` inside plain
+    /// `function`s, which rebind `this`.
+    this_class: Option<&'c str>,
+    calls: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for CallCollector<'_> {
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        match it.callee.get_inner_expression() {
+            Expression::Identifier(id) => self.calls.push((id.name.to_string(), it.span)),
+            Expression::StaticMemberExpression(m) => match (m.object.get_inner_expression(), self.this_class) {
+                (Expression::ThisExpression(_), Some(class)) => self
+                    .calls
+                    .push((format!("{}.{}", class, m.property.name), it.span)),
+                // `Class.staticMethod()` or `ns.fn()` on a namespace import.
+                (Expression::Identifier(obj), _) => self
+                    .calls
+                    .push((format!("{}.{}", obj.name, m.property.name), it.span)),
+                _ => {}
+            },
+            _ => {}
+        }
+        walk::walk_call_expression(self, it);
+    }
+
+    fn visit_function(&mut self, it: &Function<'a>, flags: ScopeFlags) {
+        let outer = self.this_class.take();
+        walk::walk_function(self, it, flags);
+        self.this_class = outer;
+    }
+
+    fn visit_class(&mut self, it: &Class<'a>) {
+        let outer = self.this_class.take();
+        walk::walk_class(self, it);
+        self.this_class = outer;
+    }
+}
+
+/// Callees that return the function passed as their first argument:
+/// `useCallback`, `useMemo`, `memo`, `forwardRef` (bare or
+/// `React.`-qualified) and `withX(...)` higher-order components.
+fn returns_first_argument(callee: &Expression<'_>) -> bool {
+    let name = match callee.get_inner_expression() {
+        Expression::Identifier(id) => id.name.as_str(),
+        Expression::StaticMemberExpression(m) => match &m.object {
+            Expression::Identifier(obj) if obj.name == "React" => m.property.name.as_str(),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    matches!(name, "useCallback" | "useMemo" | "memo" | "forwardRef")
+        || name
+            .strip_prefix("with")
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// True for initializers that make a binding a call-graph node:
+/// functions, arrows, and wrappers that return one (`memo(() => ...)`,
+/// `useCallback(...)`). Other calls taking a callback (`setTimeout`,
+/// `arr.map`) bind their result, not a function.
+pub fn is_function_like(expr: &Expression<'_>) -> bool {
+    match expr.get_inner_expression() {
+        Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_) => true,
+        Expression::CallExpression(c) => {
+            returns_first_argument(&c.callee)
+                && c.arguments
+                    .first()
+                    .and_then(|a| a.as_expression())
+                    .is_some_and(is_function_like)
+        }
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct Nodes<'a> {
+    /// (node name, `this` class, body span owner) in source order.
+    bodies: Vec<(String, Option<String>, Body<'a>)>,
+}
+
+#[derive(Clone, Copy)]
+enum Body<'a> {
+    Function(&'a Function<'a>),
+    Expression(&'a Expression<'a>),
+}
+
+impl<'a> Nodes<'a> {
+    fn class(&mut self, class: &'a Class<'a>, name: &str) {
+        for element in &class.body.body {
+            match element {
+                ClassElement::MethodDefinition(m) => {
+                    if let Some(member) = m.key.static_name() {
+                        self.bodies.push((
+                            format!("{}.{}", name, member),
+                            Some(name.to_string()),
+                            Body::Function(&m.value),
+                        ));
+                    }
+                }
+                ClassElement::PropertyDefinition(p) => {
+                    if let (Some(member), Some(value)) = (p.key.static_name(), &p.value)
+                        && is_function_like(value)
+                    {
+                        self.bodies.push((
+                            format!("{}.{}", name, member),
+                            Some(name.to_string()),
+                            Body::Expression(value),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn declaration(&mut self, decl: &'a Declaration<'a>) {
+        match decl {
+            Declaration::FunctionDeclaration(f) => {
+                if let Some(id) = &f.id {
+                    self.bodies.push((id.name.to_string(), None, Body::Function(f)));
+                }
+            }
+            Declaration::VariableDeclaration(v) => {
+                for d in &v.declarations {
+                    if let (BindingPattern::BindingIdentifier(id), Some(init)) = (&d.id, &d.init)
+                        && is_function_like(init)
+                    {
+                        self.bodies
+                            .push((id.name.to_string(), None, Body::Expression(init)));
+                    }
+                }
+            }
+            Declaration::ClassDeclaration(c) => {
+                if let Some(id) = &c.id {
+                    self.class(c, &id.name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn statement(&mut self, stmt: &'a Statement<'a>) {
+        match stmt {
+            Statement::ExportNamedDeclaration(e) => {
+                if let Some(d) = &e.declaration {
+                    self.declaration(d);
+                }
+            }
+            Statement::ExportDefaultDeclaration(e) => match &e.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(f) => {
+                    let name = f.id.as_ref().map_or("default".to_string(), |id| id.name.to_string());
+                    self.bodies.push((name, None, Body::Function(f)));
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(c) => {
+                    let name = c.id.as_ref().map_or("default", |id| id.name.as_str());
+                    self.class(c, name);
+                }
+                other => {
+                    if let Some(expr) = other.as_expression()
+                        && is_function_like(expr)
+                    {
+                        self.bodies
+                            .push(("default".to_string(), None, Body::Expression(expr)));
+                    }
+                }
+            },
+            _ => {
+                if let Some(d) = stmt.as_declaration() {
+                    self.declaration(d);
+                }
+            }
+        }
+    }
+}
+
+/// Calls made inside each top-level node, as written: `name`,
+/// `Class.member` for `this.member()`, or `object.member`. Owned, so it can be
+/// collected before function bodies are stripped and linked afterwards.
+pub struct RawCalls {
+    names: Vec<String>,
+    calls: Vec<(String, String, Span)>,
+}
+
+pub fn collect(program: &Program<'_>) -> RawCalls {
+    let mut nodes = Nodes::default();
+    for stmt in &program.body {
+        nodes.statement(stmt);
+    }
+    let mut names = Vec::new();
+    let mut calls = Vec::new();
+    for (name, this_class, body) in &nodes.bodies {
+        let mut collector = CallCollector {
+            this_class: this_class.as_deref(),
+            calls: Vec::new(),
+        };
+        match body {
+            // Walk the function's parts directly so the method's own `this`
+            // binding is kept.
+            Body::Function(f) => {
+                collector.visit_formal_parameters(&f.params);
+                if let Some(b) = &f.body {
+                    collector.visit_function_body(b);
+                }
+            }
+            Body::Expression(e) => match e.get_inner_expression() {
+                Expression::FunctionExpression(f) => {
+                    collector.visit_formal_parameters(&f.params);
+                    if let Some(b) = &f.body {
+                        collector.visit_function_body(b);
+                    }
+                }
+                _ => collector.visit_expression(e),
+            },
+        }
+        calls.extend(
+            collector
+                .calls
+                .into_iter()
+                .map(|(callee, span)| (name.clone(), callee, span)),
+        );
+        names.push(name.clone());
+    }
+    RawCalls { names, calls }
+}
+
+fn grouped<T>(
+    calls: BTreeMap<(String, String), Vec<u32>>,
+    make: impl Fn(String, String, Vec<u32>) -> T,
+) -> Vec<T> {
+    calls
+        .into_iter()
+        .map(|((caller, callee), mut lines)| {
+            lines.sort_unstable();
+            lines.dedup();
+            make(caller, callee, lines)
+        })
+        .collect()
+}
+
+impl RawCalls {
+    /// Split calls into edges between the file's own nodes and calls of
+    /// imported bindings (`name()` on a named/default import, `ns.fn()` on a
+    /// namespace import), each grouped with the sorted lines of the calls.
+    /// `line_of` maps a byte offset to its 1-based line.
+    pub fn link(
+        self,
+        imports: &[ImportRecord],
+        line_of: impl Fn(u32) -> u32,
+    ) -> (Vec<CallEdge>, Vec<ImportedCall>) {
+        let known: HashSet<&str> = self.names.iter().map(String::as_str).collect();
+        let bound = |local: &str, namespace: bool| {
+            imports.iter().filter(|r| !r.dynamic).any(|r| {
+                r.bindings
+                    .iter()
+                    .any(|b| b.local == local && (b.imported == "*") == namespace)
+            })
+        };
+        let mut local = BTreeMap::new();
+        let mut imported = BTreeMap::new();
+        for (caller, callee, span) in self.calls {
+            let line = line_of(span.start);
+            if known.contains(callee.as_str()) {
+                local.entry((caller, callee)).or_insert_with(Vec::new).push(line);
+            } else if match callee.split_once('.') {
+                None => bound(&callee, false),
+                Some((object, _)) => bound(object, true),
+            } {
+                imported
+                    .entry((caller, callee))
+                    .or_insert_with(Vec::new)
+                    .push(line);
+            }
+        }
+        (
+            grouped(local, |caller, callee, lines| CallEdge {
+                caller,
+                callee,
+                lines,
+            }),
+            grouped(imported, |caller, callee, lines| {
+                let (local, member) = match callee.split_once('.') {
+                    Some((o, m)) => (o.to_string(), Some(m.to_string())),
+                    None => (callee, None),
+                };
+                ImportedCall {
+                    caller,
+                    local,
+                    member,
+                    lines,
+                }
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::skeleton::skeletonize_source;
+    use std::path::Path;
+
+    fn edges(src: &str) -> Vec<(String, String, Vec<u32>)> {
+        skeletonize_source(src, Path::new("x.ts"))
+            .unwrap()
+            .call_graph
+            .into_iter()
+            .map(|e| (e.caller, e.callee, e.lines))
+            .collect()
+    }
+
+    #[test]
+    fn records_identifier_and_this_calls_between_top_level_nodes() {
+        let src = "\
+function normalize(s: string) { return s.trim(); }
+export const clean = (s: string) => normalize(normalize(s));
+export class Service {
+  run() {
+    this.step();
+    [1].forEach(() => this.step());
+    function inner(this: unknown) { (this as any).step(); }
+    return clean('x');
+  }
+  step = () => normalize('y');
+}
+const notAFunction = normalize('z');
+export default function () { return helper(); }
+const timer = setTimeout(() => normalize('t'));
+const doubled = [1].map(() => normalize('m'));
+const handler = useCallback(() => normalize('h'), []);
+const Page = withRouter(React.memo(() => normalize('p')));
+";
+        assert_eq!(
+            edges(src),
+            vec![
+                ("Page".into(), "normalize".into(), vec![17]),
+                ("Service.run".into(), "Service.step".into(), vec![5, 6]),
+                ("Service.run".into(), "clean".into(), vec![8]),
+                ("Service.step".into(), "normalize".into(), vec![10]),
+                ("clean".into(), "normalize".into(), vec![2]),
+                ("handler".into(), "normalize".into(), vec![16]),
+            ]
+        );
+    }
+
+    #[test]
+    fn calls_of_imported_bindings_are_kept_for_linking() {
+        let src = "\
+import { a as alias } from './a';
+import * as ns from './ns';
+import def from './def';
+import { local } from './shadowed';
+function local() {}
+export function run() {
+  alias();
+  ns.go();
+  ns.go();
+  def();
+  local();
+  other();
+}
+";
+        let ir = skeletonize_source(src, Path::new("x.ts")).unwrap();
+        let imported: Vec<(&str, Option<&str>, Vec<u32>)> = ir
+            .imported_calls
+            .iter()
+            .map(|c| (c.local.as_str(), c.member.as_deref(), c.lines.clone()))
+            .collect();
+        assert_eq!(
+            imported,
+            vec![("alias", None, vec![7]), ("def", None, vec![10]), ("ns", Some("go"), vec![8, 9])]
+        );
+        assert_eq!(ir.call_graph.len(), 1, "the local declaration wins: {:?}", ir.call_graph);
+    }
+}
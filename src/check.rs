@@ -0,0 +1,298 @@
+//! Graph invariants for CI: import cycles, architecture-rule violations,
+//! files that fail to parse and relative imports that point at nothing.
+//!
+//! Every finding carries a line-independent fingerprint, so a baseline of
+//! accepted findings keeps suppressing them while unrelated edits move them
+//! around. Output is either plain `file:line:column` diagnostics or SARIF
+//! 2.1.0 for code-scanning upload.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::architecture::{self, CONFIG_FILE};
+use crate::graph::{import_cycles, walk_targets, AppState};
+use crate::resolve::is_relative;
+use crate::skeleton::{syntax_errors, Location};
+
+/// Check ids (SARIF rule ids) and what they flag.
+pub const CHECKS: &[(&str, &str)] = &[
+    ("import-cycle", "Files import each other in a cycle"),
+    ("architecture", "An import breaks a rule in .skeletonizer.json"),
+    ("parse-error", "The file does not parse"),
+    ("unresolved-import", "A relative import points at no file"),
+];
+
+/// Targets a relative import may name outside the graph: compiled or plain
+/// JavaScript, declarations, JSON, directories with an index.
+const ON_DISK_SUFFIXES: &[&str] = &[
+    "", ".ts", ".tsx", ".d.ts", ".js", ".jsx", ".mjs", ".cjs", ".json",
+    "/index.ts", "/index.tsx", "/index.js", "/index.jsx",
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// One of `CHECKS`.
+    pub check: String,
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+    pub message: String,
+    /// Identity for baselines; does not change when the finding moves.
+    pub fingerprint: String,
+}
+
+impl Finding {
+    fn at(check: &str, file: &str, location: Location, message: String, detail: &str) -> Finding {
+        Finding {
+            check: check.to_string(),
+            file: file.to_string(),
+            line: location.start_line.max(1),
+            column: location.start_column.max(1),
+            message,
+            fingerprint: format!("{}:{}:{}", check, file, detail),
+        }
+    }
+}
+
+/// Accepted findings, as written by `save`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Baseline {
+    pub findings: Vec<Finding>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read baseline {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("invalid baseline {}", path.display()))
+    }
+
+    pub fn save(path: &Path, findings: &[Finding]) -> Result<()> {
+        let baseline = Baseline {
+            findings: findings.to_vec(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&baseline)? + "\n")
+            .with_context(|| format!("cannot write baseline {}", path.display()))
+    }
+
+    /// `findings` split into (new, suppressed by this baseline).
+    pub fn partition(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<Finding>) {
+        let known: HashSet<&str> = self.findings.iter().map(|f| f.fingerprint.as_str()).collect();
+        findings
+            .into_iter()
+            .partition(|f| !known.contains(f.fingerprint.as_str()))
+    }
+}
+
+/// True when a relative specifier names some file on disk, even one the
+/// graph doesn't hold (`./styles.css`, `./data.json`, NodeNext-style
+/// `./x.js` for `x.ts`).
+fn exists_on_disk(root: &Path, importer: &str, spec: &str) -> bool {
+    let base = root.join(importer);
+    let raw = base.parent().unwrap_or(root).join(spec);
+    let raw = raw.to_string_lossy();
+    let stem = [".js", ".jsx", ".mjs", ".cjs"]
+        .iter()
+        .find_map(|ext| raw.strip_suffix(ext));
+    ON_DISK_SUFFIXES
+        .iter()
+        .any(|suffix| Path::new(&format!("{}{}", raw, suffix)).is_file())
+        || stem.is_some_and(|stem| {
+            [".ts", ".tsx", ".d.ts"]
+                .iter()
+                .any(|ext| Path::new(&format!("{}{}", stem, ext)).is_file())
+        })
+}
+
+fn cycles(state: &AppState, out: &mut Vec<Finding>) {
+    for members in import_cycles(state) {
+        let first = &members[0];
+        let location = state
+            .skeleton_graph
+            .get(first)
+            .and_then(|node| {
+                node.import_records
+                    .iter()
+                    .find(|r| r.resolved.as_ref().is_some_and(|t| members.contains(t)))
+                    .map(|r| r.location)
+            })
+            .unwrap_or_default();
+        out.push(Finding::at(
+            "import-cycle",
+            first,
+            location,
+            format!("import cycle between {}", members.join(", ")),
+            &members.join(","),
+        ));
+    }
+}
+
+fn architecture_violations(state: &AppState, out: &mut Vec<Finding>) {
+    if let Some(error) = &state.architecture.read().unwrap().error {
+        out.push(Finding::at("architecture", CONFIG_FILE, Location::default(), error.clone(), "config"));
+    }
+    for v in architecture::violations(state) {
+        let detail = format!("{}:{}:{}", v.rule, v.source, v.target);
+        out.push(Finding::at("architecture", &v.file, v.location, v.message, &detail));
+    }
+}
+
+/// Walked files missing from the graph are the ones the sweep could not
+/// parse (or read). Only the first syntax error is reported; the parser's
+/// later ones usually cascade from it.
+fn parse_errors(state: &AppState, out: &mut Vec<Finding>) {
+    for (key, path) in walk_targets(state, &state.root) {
+        if state.skeleton_graph.contains_key(&key) {
+            continue;
+        }
+        let (message, location) = match std::fs::read_to_string(&path) {
+            Ok(source) => syntax_errors(&source, &path)
+                .into_iter()
+                .next()
+                .unwrap_or_else(|| ("failed to parse module".to_string(), Location::default())),
+            Err(e) => (format!("cannot read file: {}", e), Location::default()),
+        };
+        let detail = format!("parse:{}", message);
+        out.push(Finding::at("parse-error", &key, location, message, &detail));
+    }
+}
+
+fn unresolved_imports(state: &AppState, out: &mut Vec<Finding>) {
+    for entry in state.skeleton_graph.iter() {
+        for record in &entry.value().import_records {
+            if record.resolved.is_none()
+                && is_relative(&record.source)
+                && !exists_on_disk(&state.root, entry.key(), &record.source)
+            {
+                out.push(Finding::at(
+                    "unresolved-import",
+                    entry.key(),
+                    record.location,
+                    format!("cannot resolve '{}'", record.source),
+                    &record.source,
+                ));
+            }
+        }
+    }
+}
+
+/// Every finding over the swept graph, by file then position. Reloads the
+/// architecture rules first.
+pub fn run(state: &AppState) -> Vec<Finding> {
+    let _gate = state.batch_gate.read().unwrap();
+    architecture::reload(state);
+    let mut findings = Vec::new();
+    cycles(state, &mut findings);
+    architecture_violations(state, &mut findings);
+    parse_errors(state, &mut findings);
+    unresolved_imports(state, &mut findings);
+    findings.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.check).cmp(&(&b.file, b.line, b.column, &b.check))
+    });
+    findings
+}
+
+/// SARIF 2.1.0 log of `findings`, with `suppressed` ones marked as
+/// suppressed externally (by the baseline). `prefix` is the root's path
+/// inside the repository (see `git::prefix`), so URIs are
+/// repository-relative as code scanning expects.
+pub fn sarif(findings: &[Finding], suppressed: &[Finding], prefix: &str) -> Value {
+    let result = |f: &Finding, suppressed: bool| {
+        let mut result = json!({
+            "ruleId": f.check,
+            "level": "error",
+            "message": {"text": f.message},
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": {"uri": format!("{}{}", prefix, f.file), "uriBaseId": "%SRCROOT%"},
+                    "region": {"startLine": f.line, "startColumn": f.column},
+                },
+            }],
+            "partialFingerprints": {"skeletonizer/v1": f.fingerprint},
+        });
+        if suppressed {
+            result["suppressions"] = json!([{"kind": "external"}]);
+        }
+        result
+    };
+    let rules: Vec<Value> = CHECKS
+        .iter()
+        .map(|(id, description)| json!({"id": id, "shortDescription": {"text": description}}))
+        .collect();
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|f| result(f, false))
+        .chain(suppressed.iter().map(|f| result(f, true)))
+        .collect();
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {"driver": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+                "rules": rules,
+            }},
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn flags_cycles_violations_parse_errors_and_broken_imports() {
+        let (_dir, state) = project(&[
+            (CONFIG_FILE, r#"{ "architecture": { "rules": [ { "name": "no-db", "from": ["src/ui/"], "disallow": ["src/db/"] } ] } }"#),
+            ("src/a.ts", "import { b } from './b';\nexport const a = 1;\n"),
+            ("src/b.ts", "export const x = 1;\nimport { a } from './a';\nexport const b = 1;\n"),
+            ("src/db/q.ts", "export const q = 1;\n"),
+            (
+                "src/ui/Page.ts",
+                "import { q } from '../db/q';\nimport './page.css';\nimport { h } from './helpers.js';\nimport { gone } from './gone';\n",
+            ),
+            ("src/ui/page.css", ""),
+            ("src/ui/helpers.ts", "export const h = 1;\n"),
+            ("src/broken.ts", "export const ok = 1;\nexport function (\n"),
+        ]);
+        let found: Vec<(String, String, u32)> = run(&state)
+            .into_iter()
+            .map(|f| (f.check, f.file, f.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("import-cycle".to_string(), "src/a.ts".to_string(), 1),
+                ("parse-error".to_string(), "src/broken.ts".to_string(), 2),
+                ("architecture".to_string(), "src/ui/Page.ts".to_string(), 1),
+                ("unresolved-import".to_string(), "src/ui/Page.ts".to_string(), 4),
+            ]
+        );
+
+        let findings = run(&state);
+        // Different syntax errors in one file are different findings.
+        assert_eq!(findings[1].fingerprint, format!("parse-error:src/broken.ts:parse:{}", findings[1].message));
+        // A baselined finding that has since moved is still suppressed.
+        let mut moved = findings[0].clone();
+        moved.line += 2;
+        let baseline = Baseline {
+            findings: vec![moved, findings[1].clone()],
+        };
+        let (fresh, suppressed) = baseline.partition(findings);
+        assert_eq!((fresh.len(), suppressed.len()), (2, 2));
+
+        let log = sarif(&fresh, &suppressed, "web/");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "web/src/ui/Page.ts");
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 1);
+        assert!(results[0]["suppressions"].is_null());
+        assert_eq!(results[3]["suppressions"][0]["kind"], "external");
+    }
+}
@@ -0,0 +1,222 @@
+//! Change history joined with the import graph.
+//!
+//! Churn is the number of commits touching a file; co-change counts how
+//! often two files are committed together. Pairs that keep changing together
+//! without an import edge between them are hidden coupling; files that both
+//! change often and are imported widely are hotspots. Only files currently
+//! in the graph are counted.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+
+use crate::git::{self, Commit};
+use crate::graph::AppState;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Options {
+    /// Newest commits to walk.
+    pub max_commits: usize,
+    /// Only commits after this date (`git log --since` syntax).
+    pub since: Option<String>,
+    /// Co-change count below which a pair is not reported.
+    pub min_co_changes: usize,
+    /// Commits touching more graph files than this (formatting sweeps,
+    /// codemods) count toward churn but not co-change.
+    pub max_commit_files: usize,
+    /// Rows per list.
+    pub limit: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_commits: 500,
+            since: None,
+            min_co_changes: 3,
+            max_commit_files: 30,
+            limit: 20,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FileChurn {
+    pub file: String,
+    pub commits: usize,
+    /// Committer time of the newest commit, seconds since the epoch.
+    pub last_changed: i64,
+    pub fan_in: usize,
+    pub fan_out: usize,
+    /// `commits × fan_in`: how much change ripples to importers.
+    pub score: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CoChange {
+    pub a: String,
+    pub b: String,
+    /// Commits touching both.
+    pub together: usize,
+    /// `together` over the pair's average churn, 0–1.
+    pub degree: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Report {
+    /// Commits walked.
+    pub commits: usize,
+    /// Most-changed files.
+    pub churn: Vec<FileChurn>,
+    /// Highest `score` first; files nothing imports are left out.
+    pub hotspots: Vec<FileChurn>,
+    /// Co-changing pairs with no import edge in either direction.
+    pub hidden_coupling: Vec<CoChange>,
+}
+
+pub fn report(state: &AppState, opts: &Options) -> Result<Report> {
+    let commits = git::log(&state.root, opts.max_commits, opts.since.as_deref())?;
+    Ok(analyze(state, &commits, opts))
+}
+
+pub fn analyze(state: &AppState, commits: &[Commit], opts: &Options) -> Report {
+    let mut churn: HashMap<&str, (usize, i64)> = HashMap::new();
+    let mut pairs: HashMap<(&str, &str), usize> = HashMap::new();
+    for commit in commits {
+        let files: BTreeSet<&str> = commit
+            .files
+            .iter()
+            .map(String::as_str)
+            .filter(|f| state.skeleton_graph.contains_key(*f))
+            .collect();
+        for file in &files {
+            let entry = churn.entry(file).or_insert((0, commit.timestamp));
+            entry.0 += 1;
+            entry.1 = entry.1.max(commit.timestamp);
+        }
+        if files.len() > opts.max_commit_files {
+            continue;
+        }
+        let files: Vec<&str> = files.into_iter().collect();
+        for (i, a) in files.iter().enumerate() {
+            for b in &files[i + 1..] {
+                *pairs.entry((a, b)).or_default() += 1;
+            }
+        }
+    }
+
+    let mut files: Vec<FileChurn> = churn
+        .iter()
+        .map(|(file, (commits, last_changed))| {
+            let fan_in = state.dependents_of(file).len();
+            FileChurn {
+                file: file.to_string(),
+                commits: *commits,
+                last_changed: *last_changed,
+                fan_in,
+                fan_out: state
+                    .skeleton_graph
+                    .get(*file)
+                    .map(|n| n.dependencies.len())
+                    .unwrap_or(0),
+                score: commits * fan_in,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.file.cmp(&b.file)));
+    let mut hotspots: Vec<FileChurn> = files.iter().filter(|f| f.score > 0).cloned().collect();
+    hotspots.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.file.cmp(&b.file)));
+    hotspots.truncate(opts.limit);
+    files.truncate(opts.limit);
+
+    let imports = |from: &str, to: &str| {
+        state
+            .skeleton_graph
+            .get(from)
+            .is_some_and(|n| n.dependencies.iter().any(|d| d == to))
+    };
+    let mut hidden: Vec<CoChange> = pairs
+        .into_iter()
+        .filter(|((a, b), together)| {
+            *together >= opts.min_co_changes.max(1) && !imports(a, b) && !imports(b, a)
+        })
+        .map(|((a, b), together)| {
+            let average = (churn[a].0 + churn[b].0) as f64 / 2.0;
+            CoChange {
+                a: a.to_string(),
+                b: b.to_string(),
+                together,
+                degree: (together as f64 / average * 100.0).round() / 100.0,
+            }
+        })
+        .collect();
+    hidden.sort_by(|x, y| {
+        y.together
+            .cmp(&x.together)
+            .then_with(|| y.degree.total_cmp(&x.degree))
+            .then_with(|| (&x.a, &x.b).cmp(&(&y.a, &y.b)))
+    });
+    hidden.truncate(opts.limit);
+
+    Report {
+        commits: commits.len(),
+        churn: files,
+        hotspots,
+        hidden_coupling: hidden,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn joins_churn_and_co_change_with_import_edges() {
+        let (_dir, state) = project(&[
+            ("src/core.ts", "export const core = 1;\n"),
+            ("src/a.ts", "import { core } from './core';\n"),
+            ("src/b.ts", "import { core } from './core';\n"),
+            ("src/schema.ts", "export const schema = 1;\n"),
+        ]);
+        let commit = |t: i64, files: &[&str]| Commit {
+            hash: format!("{:040}", t),
+            timestamp: t,
+            files: files.iter().map(|f| f.to_string()).collect(),
+        };
+        let commits = vec![
+            commit(5, &["src/a.ts", "src/schema.ts", "README.md"]),
+            commit(4, &["src/a.ts", "src/schema.ts", "src/core.ts"]),
+            commit(3, &["src/a.ts", "src/core.ts"]),
+            commit(2, &["src/core.ts", "src/gone.ts"]),
+            commit(1, &["src/a.ts", "src/b.ts", "src/core.ts", "src/schema.ts"]),
+        ];
+        let opts = Options {
+            min_co_changes: 2,
+            max_commit_files: 3,
+            ..Options::default()
+        };
+        let report = analyze(&state, &commits, &opts);
+        assert_eq!(report.commits, 5);
+
+        let churn: Vec<(&str, usize)> = report.churn.iter().map(|f| (f.file.as_str(), f.commits)).collect();
+        assert_eq!(churn, [("src/a.ts", 4), ("src/core.ts", 4), ("src/schema.ts", 3), ("src/b.ts", 1)]);
+        assert_eq!(report.churn[0].last_changed, 5);
+        let core = &report.hotspots[0];
+        assert_eq!((core.file.as_str(), core.fan_in, core.score), ("src/core.ts", 2, 8));
+        assert_eq!(report.hotspots.len(), 1);
+
+        // a.ts imports core.ts, so only a/schema is hidden; the four-file
+        // commit is too broad to count.
+        assert_eq!(
+            report.hidden_coupling,
+            vec![CoChange {
+                a: "src/a.ts".into(),
+                b: "src/schema.ts".into(),
+                together: 2,
+                degree: 0.57,
+            }]
+        );
+    }
+}
@@ -0,0 +1,281 @@
+//! One-shot subcommands for scripts and CI: sweep the root once, print the
+//! same JSON the MCP tools and resources return (or, for `check`, graph
+//! invariant diagnostics), and exit. No watcher, no dashboard, no stdio
+//! protocol.
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::check::{self, Baseline};
+use crate::git;
+use crate::graph::{perform_initial_sweep, AppState};
+use crate::protocol;
+
+pub const USAGE: &str = "usage: semantic_skeletonizer [--root DIR] [COMMAND]
+
+Without a command, serves MCP over stdio.
+
+Commands:
+  dump [FILE]        Print the global skeleton graph, or one file's skeleton
+  symbols FILE       Print a file's symbols (list_symbols)
+  deps FILE          Print a file's resolved import edges (get_dependencies)
+  impl FILE NODE     Print the source of one named node (get_implementation)
+  check              Report import cycles, architecture violations, parse
+                     errors and unresolved relative imports
+
+Check options:
+  --sarif PATH       Also write the findings as SARIF 2.1.0
+  --baseline PATH    Suppress the findings recorded in PATH
+  --write-baseline   Record the current findings in the --baseline file
+
+Exit codes: 0 success, 1 lookup failure or check findings, 2 usage error
+(or, for check, an unreadable baseline or unwritable output).";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Serve,
+    Help,
+    Dump { file: Option<String> },
+    Symbols { file: String },
+    Deps { file: String },
+    Impl { file: String, node: String },
+    Check {
+        sarif: Option<String>,
+        baseline: Option<String>,
+        write_baseline: bool,
+    },
+}
+
+/// The subcommand in `args` (program name first); `--root DIR` may appear
+/// anywhere.
+pub fn parse(args: &[String]) -> Result<Command> {
+    let mut positional = Vec::new();
+    let (mut sarif, mut baseline, mut write_baseline) = (None, None, false);
+    let mut rest = args.iter().skip(1);
+    while let Some(arg) = rest.next() {
+        let mut value = |flag: &str| match rest.next() {
+            Some(value) => Ok(Some(value.clone())),
+            None => Err(anyhow::anyhow!("{} requires PATH", flag)),
+        };
+        match arg.as_str() {
+            "--root" => {
+                rest.next();
+            }
+            "--help" | "-h" => return Ok(Command::Help),
+            "--sarif" => sarif = value(arg)?,
+            "--baseline" => baseline = value(arg)?,
+            "--write-baseline" => write_baseline = true,
+            flag if flag.starts_with("--") => bail!("unknown option {}", flag),
+            _ => positional.push(arg.clone()),
+        }
+    }
+    let mut positional = positional.into_iter();
+    let command = positional.next();
+    let mut operand = |what: &str| -> Result<String> {
+        match positional.next() {
+            Some(value) => Ok(value),
+            None => bail!("{} requires {}", command.as_deref().unwrap_or(""), what),
+        }
+    };
+    let parsed = match command.as_deref() {
+        None => Command::Serve,
+        Some("dump") => Command::Dump { file: positional.next() },
+        Some("symbols") => Command::Symbols { file: operand("FILE")? },
+        Some("deps") => Command::Deps { file: operand("FILE")? },
+        Some("impl") => Command::Impl {
+            file: operand("FILE")?,
+            node: operand("NODE")?,
+        },
+        Some("check") => {
+            if write_baseline && baseline.is_none() {
+                bail!("--write-baseline requires --baseline PATH");
+            }
+            Command::Check {
+                sarif: sarif.take(),
+                baseline: baseline.take(),
+                write_baseline: std::mem::take(&mut write_baseline),
+            }
+        }
+        Some(other) => bail!("unknown command {}", other),
+    };
+    if let Some(extra) = positional.next() {
+        bail!("unexpected argument {}", extra);
+    }
+    if sarif.is_some() || baseline.is_some() || write_baseline {
+        bail!("--sarif, --baseline and --write-baseline only apply to check");
+    }
+    Ok(parsed)
+}
+
+/// Text of the first content block of a tool result or resource read.
+fn first_text(result: &Value, field: &str) -> String {
+    result[field][0]["text"].as_str().unwrap_or_default().to_string()
+}
+
+/// Run the graph checks and report them: diagnostics on stdout, SARIF to
+/// `sarif`, findings recorded in `baseline` suppressed (or, with
+/// `write_baseline`, the current findings recorded there instead).
+fn run_check(
+    state: &AppState,
+    sarif: Option<&str>,
+    baseline: Option<&str>,
+    write_baseline: bool,
+) -> Result<i32> {
+    let findings = check::run(state);
+    if write_baseline && let Some(path) = baseline {
+        Baseline::save(Path::new(path), &findings)?;
+        println!("recorded {} findings in {}", findings.len(), path);
+        return Ok(0);
+    }
+    let (findings, suppressed) = match baseline {
+        Some(path) => Baseline::load(Path::new(path))?.partition(findings),
+        None => (findings, Vec::new()),
+    };
+    if let Some(path) = sarif {
+        // Outside a repository, root-relative URIs are the best available.
+        let prefix = git::prefix(&state.root).unwrap_or_default();
+        let log = serde_json::to_string_pretty(&check::sarif(&findings, &suppressed, &prefix))?;
+        std::fs::write(path, log + "\n")
+            .map_err(|e| anyhow::anyhow!("cannot write SARIF to {}: {}", path, e))?;
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    for f in &findings {
+        writeln!(stdout, "{}:{}:{}: {}: {}", f.file, f.line, f.column, f.check, f.message)?;
+    }
+    let count = |n: usize| format!("{} finding{}", n, if n == 1 { "" } else { "s" });
+    let summary = match suppressed.len() {
+        0 => count(findings.len()),
+        s => format!("{} ({} suppressed by baseline)", count(findings.len()), s),
+    };
+    writeln!(stdout, "{}", summary)?;
+    stdout.flush()?;
+    Ok(if findings.is_empty() { 0 } else { 1 })
+}
+
+/// Sweep, run `command`, print its output and return the exit code.
+pub async fn run(state: Arc<AppState>, command: Command) -> i32 {
+    if !matches!(command, Command::Help) {
+        let sweep_state = state.clone();
+        let _ = tokio::task::spawn_blocking(move || perform_initial_sweep(&sweep_state)).await;
+    }
+
+    let tool = |name: &str, arguments: Value| json!({"name": name, "arguments": arguments});
+    let outcome = match command {
+        Command::Serve => return 0,
+        Command::Check {
+            sarif,
+            baseline,
+            write_baseline,
+        } => {
+            let outcome = tokio::task::spawn_blocking(move || {
+                run_check(&state, sarif.as_deref(), baseline.as_deref(), write_baseline)
+            })
+            .await;
+            return match outcome {
+                Ok(Ok(code)) => code,
+                Ok(Err(e)) => {
+                    eprintln!("{:#}", e);
+                    2
+                }
+                Err(e) => {
+                    eprintln!("check task failed: {}", e);
+                    2
+                }
+            };
+        }
+        Command::Help => Ok((USAGE.to_string(), false)),
+        Command::Dump { file } => {
+            let uri = match file {
+                Some(file) => {
+                    let key = state.key_for(&file).unwrap_or(file);
+                    protocol::file_uri(&key)
+                }
+                None => protocol::GLOBAL_URI.to_string(),
+            };
+            protocol::handle_resources_read(&state, Some(&json!({"uri": uri})))
+                .await
+                .map(|r| (first_text(&r, "contents"), false))
+        }
+        Command::Symbols { file } => {
+            let params = tool("list_symbols", json!({"file_path": file}));
+            protocol::handle_tools_call(&state, Some(&params))
+                .await
+                .map(|r| (first_text(&r, "content"), r["isError"] == true))
+        }
+        Command::Deps { file } => {
+            let params = tool("get_dependencies", json!({"file_path": file}));
+            protocol::handle_tools_call(&state, Some(&params))
+                .await
+                .map(|r| (first_text(&r, "content"), r["isError"] == true))
+        }
+        Command::Impl { file, node } => {
+            let params = tool("get_implementation", json!({"file_path": file, "target_node": node}));
+            protocol::handle_tools_call(&state, Some(&params))
+                .await
+                .map(|r| (first_text(&r, "content"), r["isError"] == true))
+        }
+    };
+    match outcome {
+        Ok((text, false)) => {
+            let mut stdout = std::io::stdout().lock();
+            let _ = writeln!(stdout, "{}", text);
+            let _ = stdout.flush();
+            0
+        }
+        Ok((text, true)) => {
+            eprintln!("{}", text);
+            1
+        }
+        Err(error) => {
+            eprintln!("{}", error["message"].as_str().unwrap_or("request failed"));
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        std::iter::once("bin").chain(line.split_whitespace()).map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parses_subcommands_around_the_root_flag() {
+        assert_eq!(parse(&args("--root /repo")).unwrap(), Command::Serve);
+        assert_eq!(parse(&args("dump")).unwrap(), Command::Dump { file: None });
+        assert_eq!(
+            parse(&args("--root /repo dump src/a.ts")).unwrap(),
+            Command::Dump { file: Some("src/a.ts".into()) }
+        );
+        assert_eq!(
+            parse(&args("impl src/a.ts A.b --root .")).unwrap(),
+            Command::Impl { file: "src/a.ts".into(), node: "A.b".into() }
+        );
+        assert!(parse(&args("symbols")).unwrap_err().to_string().contains("requires FILE"));
+        assert!(parse(&args("deps a.ts b.ts")).is_err());
+        assert!(parse(&args("frobnicate")).is_err());
+        assert!(parse(&args("dump --pretty")).is_err());
+        assert_eq!(parse(&args("deps -h")).unwrap(), Command::Help);
+        assert_eq!(
+            parse(&args("check --baseline b.json --sarif out.sarif")).unwrap(),
+            Command::Check {
+                sarif: Some("out.sarif".into()),
+                baseline: Some("b.json".into()),
+                write_baseline: false,
+            }
+        );
+        assert!(matches!(
+            parse(&args("check --write-baseline --baseline b.json")).unwrap(),
+            Command::Check { write_baseline: true, .. }
+        ));
+        assert!(parse(&args("check --write-baseline")).is_err());
+        assert!(parse(&args("check --sarif")).is_err());
+        assert!(parse(&args("dump --sarif x")).is_err());
+    }
+}
@@ -0,0 +1,558 @@
+//! React component records.
+//!
+//! For each top-level component (the same predicate `symbols` uses: a
+//! PascalCase function, arrow or `memo`/`forwardRef`-wrapped binding in a
+//! `.tsx` file, or an `FC`-annotated one) this records its props type,
+//! destructuring defaults, hooks called, wrappers and the child components it
+//! renders. Extraction runs before function bodies are stripped.
+
+use anyhow::{Context, Result};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::*;
+use oxc_ast_visit::{walk, Visit};
+use oxc_span::GetSpan;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::skeleton::{annotation_is_fc, is_pascal_case, parse_source, Location};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComponentRecord {
+    pub name: String,
+    /// `memo` / `forwardRef`, outermost first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrappers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub props: Option<PropsType>,
+    /// Prop name -> default value source, from destructuring defaults.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub default_props: BTreeMap<String, String>,
+    /// Hooks called, in first-call order (`useState`, `useQuery`, ...).
+    #[serde(default)]
+    pub hooks: Vec<String>,
+    /// Child components rendered as JSX (`Button`, `Form.Field`), in first-use
+    /// order; intrinsic elements are left out.
+    #[serde(default)]
+    pub renders: Vec<String>,
+    #[serde(default)]
+    pub location: Location,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PropsType {
+    /// Referenced type as written (`ButtonProps`); `None` for an inline
+    /// type literal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    /// Graph key declaring `type_name` when it is imported (filled in at
+    /// query time).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defined_in: Option<String>,
+    /// Own members of the props type, when it is an inline literal, an
+    /// interface or an object type alias.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<PropField>,
+    /// Types it extends or intersects with (`ButtonHTMLAttributes<...>`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PropField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub optional: bool,
+}
+
+fn text<'s>(source_text: &'s str, node: &impl GetSpan) -> &'s str {
+    let span = node.span();
+    &source_text[span.start as usize..span.end as usize]
+}
+
+/// `memo` / `forwardRef` (bare or `React.`-qualified) as a wrapper name.
+fn wrapper_name(callee: &Expression<'_>) -> Option<&'static str> {
+    let name = match callee.get_inner_expression() {
+        Expression::Identifier(id) => id.name.as_str(),
+        Expression::StaticMemberExpression(m) => match &m.object {
+            Expression::Identifier(obj) if obj.name == "React" => m.property.name.as_str(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    match name {
+        "memo" => Some("memo"),
+        "forwardRef" => Some("forwardRef"),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum FnLike<'b, 'a> {
+    Arrow(&'b ArrowFunctionExpression<'a>),
+    Function(&'b Function<'a>),
+}
+
+impl<'b, 'a> FnLike<'b, 'a> {
+    fn params(self) -> &'b FormalParameters<'a> {
+        match self {
+            FnLike::Arrow(f) => &f.params,
+            FnLike::Function(f) => &f.params,
+        }
+    }
+
+    fn body(self) -> Option<&'b FunctionBody<'a>> {
+        match self {
+            FnLike::Arrow(f) => Some(&f.body),
+            FnLike::Function(f) => f.body.as_deref(),
+        }
+    }
+}
+
+/// A component initializer with its wrappers peeled off.
+pub struct Unwrapped<'b, 'a> {
+    wrappers: Vec<&'static str>,
+    /// `forwardRef<Ref, Props>`'s props type argument.
+    wrapper_props: Option<&'b TSType<'a>>,
+    function: FnLike<'b, 'a>,
+}
+
+/// Peel `memo(...)` / `forwardRef(...)` layers down to the component
+/// function; `None` when the initializer is not a function.
+pub fn unwrap_component<'b, 'a>(expr: &'b Expression<'a>) -> Option<Unwrapped<'b, 'a>> {
+    let mut wrappers = Vec::new();
+    let mut wrapper_props = None;
+    let mut expr = expr;
+    loop {
+        match expr.get_inner_expression() {
+            Expression::ArrowFunctionExpression(f) => {
+                return Some(Unwrapped {
+                    wrappers,
+                    wrapper_props,
+                    function: FnLike::Arrow(f),
+                });
+            }
+            Expression::FunctionExpression(f) => {
+                return Some(Unwrapped {
+                    wrappers,
+                    wrapper_props,
+                    function: FnLike::Function(f),
+                });
+            }
+            Expression::CallExpression(c) => {
+                let wrapper = wrapper_name(&c.callee)?;
+                if wrapper == "forwardRef"
+                    && let Some(args) = &c.type_arguments
+                {
+                    wrapper_props = wrapper_props.or(args.params.get(1));
+                }
+                wrappers.push(wrapper);
+                expr = c.arguments.first()?.as_expression()?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Top-level interfaces and type aliases, for resolving a props type
+/// declared in the same file.
+#[derive(Default)]
+struct LocalTypes<'b, 'a> {
+    interfaces: HashMap<&'b str, &'b TSInterfaceDeclaration<'a>>,
+    aliases: HashMap<&'b str, &'b TSTypeAliasDeclaration<'a>>,
+}
+
+impl<'b, 'a> LocalTypes<'b, 'a> {
+    fn new(program: &'b Program<'a>) -> Self {
+        let mut types = LocalTypes::default();
+        for stmt in &program.body {
+            let decl = match stmt {
+                Statement::ExportNamedDeclaration(e) => e.declaration.as_ref(),
+                _ => stmt.as_declaration(),
+            };
+            match decl {
+                Some(Declaration::TSInterfaceDeclaration(i)) => {
+                    types.interfaces.insert(i.id.name.as_str(), i);
+                }
+                Some(Declaration::TSTypeAliasDeclaration(t)) => {
+                    types.aliases.insert(t.id.name.as_str(), t);
+                }
+                _ => {}
+            }
+        }
+        types
+    }
+
+    /// Fields and heritage of the type `name` declared here, if any.
+    fn resolve(&self, name: &str, source_text: &str) -> Option<PropsType> {
+        if let Some(i) = self.interfaces.get(name) {
+            let mut props = PropsType {
+                type_name: Some(name.to_string()),
+                fields: signature_fields(&i.body.body, source_text),
+                ..Default::default()
+            };
+            props.extends = i
+                .extends
+                .iter()
+                .map(|h| text(source_text, h).to_string())
+                .collect();
+            return Some(props);
+        }
+        let alias = self.aliases.get(name)?;
+        let mut props = PropsType {
+            type_name: Some(name.to_string()),
+            ..Default::default()
+        };
+        type_members(&alias.type_annotation, source_text, &mut props);
+        Some(props)
+    }
+}
+
+fn signature_fields(members: &[TSSignature<'_>], source_text: &str) -> Vec<PropField> {
+    members
+        .iter()
+        .filter_map(|m| match m {
+            TSSignature::TSPropertySignature(p) => Some(PropField {
+                name: p.key.static_name()?.into_owned(),
+                ty: p
+                    .type_annotation
+                    .as_ref()
+                    .map(|t| text(source_text, &t.type_annotation).to_string())
+                    .unwrap_or_else(|| "any".to_string()),
+                optional: p.optional,
+            }),
+            TSSignature::TSMethodSignature(m) => {
+                let name = m.key.static_name()?.into_owned();
+                let full = text(source_text, m.as_ref());
+                Some(PropField {
+                    ty: full[full.find('(').unwrap_or(0)..].trim_end_matches([';', ',']).to_string(),
+                    name,
+                    optional: m.optional,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Object-literal members and intersected names of a props type.
+fn type_members(ty: &TSType<'_>, source_text: &str, props: &mut PropsType) {
+    match ty {
+        TSType::TSTypeLiteral(l) => props
+            .fields
+            .extend(signature_fields(&l.members, source_text)),
+        TSType::TSIntersectionType(i) => {
+            for t in &i.types {
+                type_members(t, source_text, props);
+            }
+        }
+        TSType::TSParenthesizedType(p) => type_members(&p.type_annotation, source_text, props),
+        other => props.extends.push(text(source_text, other).to_string()),
+    }
+}
+
+fn props_from_type(ty: &TSType<'_>, types: &LocalTypes, source_text: &str) -> PropsType {
+    if let TSType::TSTypeReference(r) = ty
+        && r.type_arguments.is_none()
+        && let TSTypeName::IdentifierReference(id) = &r.type_name
+        && let Some(local) = types.resolve(&id.name, source_text)
+    {
+        return local;
+    }
+    match ty {
+        TSType::TSTypeReference(r) => PropsType {
+            type_name: Some(text(source_text, r.as_ref()).to_string()),
+            ..Default::default()
+        },
+        other => {
+            let mut props = PropsType::default();
+            type_members(other, source_text, &mut props);
+            props
+        }
+    }
+}
+
+/// `React.FC<P>` / `FC<P>` / `FunctionComponent<P>` annotation's `P`.
+fn fc_props<'b, 'a>(decl: &'b VariableDeclarator<'a>) -> Option<&'b TSType<'a>> {
+    let TSType::TSTypeReference(r) = &decl.type_annotation.as_ref()?.type_annotation else {
+        return None;
+    };
+    let name = match &r.type_name {
+        TSTypeName::IdentifierReference(id) => id.name.as_str(),
+        TSTypeName::QualifiedName(q) => match &q.left {
+            TSTypeName::IdentifierReference(ns) if ns.name == "React" => q.right.name.as_str(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if !matches!(name, "FC" | "FunctionComponent") {
+        return None;
+    }
+    r.type_arguments.as_ref()?.params.first()
+}
+
+#[derive(Default)]
+struct BodyScan {
+    hooks: Vec<String>,
+    renders: Vec<String>,
+}
+
+fn is_hook(name: &str) -> bool {
+    name == "use"
+        || name
+            .strip_prefix("use")
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_ascii_uppercase())
+}
+
+fn push_unique(list: &mut Vec<String>, item: String) {
+    if !list.contains(&item) {
+        list.push(item);
+    }
+}
+
+fn jsx_member_name(m: &JSXMemberExpression<'_>) -> Option<String> {
+    let object = match &m.object {
+        JSXMemberExpressionObject::IdentifierReference(id) => id.name.to_string(),
+        JSXMemberExpressionObject::MemberExpression(inner) => jsx_member_name(inner)?,
+        _ => return None,
+    };
+    Some(format!("{}.{}", object, m.property.name))
+}
+
+/// Name of a capitalized JSX element (`Button`, `Form.Field`); `None` for
+/// intrinsic elements like `<div>`.
+pub fn component_name(name: &JSXElementName<'_>) -> Option<String> {
+    match name {
+        JSXElementName::IdentifierReference(id) => Some(id.name.to_string()),
+        JSXElementName::MemberExpression(m) => jsx_member_name(m),
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for BodyScan {
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        match it.callee.get_inner_expression() {
+            Expression::Identifier(id) if is_hook(&id.name) => {
+                push_unique(&mut self.hooks, id.name.to_string())
+            }
+            Expression::StaticMemberExpression(m) if is_hook(&m.property.name) => {
+                push_unique(&mut self.hooks, m.property.name.to_string())
+            }
+            _ => {}
+        }
+        walk::walk_call_expression(self, it);
+    }
+
+    fn visit_jsx_opening_element(&mut self, it: &JSXOpeningElement<'a>) {
+        if let Some(name) = component_name(&it.name) {
+            push_unique(&mut self.renders, name);
+        }
+        walk::walk_jsx_opening_element(self, it);
+    }
+}
+
+fn default_props(params: &FormalParameters<'_>, source_text: &str) -> BTreeMap<String, String> {
+    let mut out = BTreeMap::new();
+    if let Some(BindingPattern::ObjectPattern(o)) = params.items.first().map(|p| &p.pattern) {
+        for prop in &o.properties {
+            if let (Some(key), BindingPattern::AssignmentPattern(a)) =
+                (prop.key.static_name(), &prop.value)
+            {
+                out.insert(key.into_owned(), text(source_text, &a.right).to_string());
+            }
+        }
+    }
+    out
+}
+
+fn record(
+    name: &str,
+    function: FnLike<'_, '_>,
+    wrappers: Vec<&'static str>,
+    fallback_props: Option<&TSType<'_>>,
+    types: &LocalTypes,
+    source_text: &str,
+) -> ComponentRecord {
+    let params = function.params();
+    let props_type = params
+        .items
+        .first()
+        .and_then(|p| p.type_annotation.as_ref())
+        .map(|t| &t.type_annotation)
+        .or(fallback_props);
+    let mut scan = BodyScan::default();
+    if let Some(body) = function.body() {
+        scan.visit_function_body(body);
+    }
+    ComponentRecord {
+        name: name.to_string(),
+        wrappers: wrappers.into_iter().map(str::to_string).collect(),
+        props: props_type.map(|t| props_from_type(t, types, source_text)),
+        default_props: default_props(params, source_text),
+        hooks: scan.hooks,
+        renders: scan.renders,
+        location: Location::default(),
+    }
+}
+
+fn declaration_components(
+    decl: &Declaration<'_>,
+    types: &LocalTypes,
+    source_text: &str,
+    out: &mut Vec<ComponentRecord>,
+) {
+    match decl {
+        Declaration::FunctionDeclaration(f) => {
+            if let Some(id) = &f.id
+                && is_pascal_case(&id.name)
+                && f.body.is_some()
+            {
+                out.push(record(&id.name, FnLike::Function(f), Vec::new(), None, types, source_text));
+            }
+        }
+        Declaration::VariableDeclaration(v) => {
+            for d in &v.declarations {
+                let (Some(name), Some(init)) = (d.id.get_identifier_name(), &d.init) else {
+                    continue;
+                };
+                if !(is_pascal_case(&name) || annotation_is_fc(d, source_text)) {
+                    continue;
+                }
+                if let Some(u) = unwrap_component(init) {
+                    let fallback = u.wrapper_props.or_else(|| fc_props(d));
+                    out.push(record(&name, u.function, u.wrappers, fallback, types, source_text));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Component records for a `.tsx` program, in source order.
+pub fn extract(program: &Program<'_>, source_text: &str) -> Vec<ComponentRecord> {
+    let types = LocalTypes::new(program);
+    let mut out = Vec::new();
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(e) => {
+                if let Some(d) = &e.declaration {
+                    declaration_components(d, &types, source_text, &mut out);
+                }
+            }
+            Statement::ExportDefaultDeclaration(e) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(f) = &e.declaration
+                    && let Some(id) = &f.id
+                    && is_pascal_case(&id.name)
+                {
+                    out.push(record(&id.name, FnLike::Function(f), Vec::new(), None, &types, source_text));
+                }
+            }
+            _ => {
+                if let Some(d) = stmt.as_declaration() {
+                    declaration_components(d, &types, source_text, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Fields of the interfaces or type aliases `names` declared in another
+/// file, for props types components import; one parse for all of them.
+pub fn props_from_file(path: &Path, names: &[&str]) -> Result<HashMap<String, PropsType>> {
+    let allocator = Allocator::default();
+    let source_text = std::fs::read_to_string(path).context("failed to load file")?;
+    let program = parse_source(&allocator, &source_text, path)?;
+    let types = LocalTypes::new(&program);
+    Ok(names
+        .iter()
+        .filter_map(|name| Some((name.to_string(), types.resolve(name, &source_text)?)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skeleton::skeletonize_source;
+
+    fn components(src: &str) -> Vec<ComponentRecord> {
+        skeletonize_source(src, Path::new("x.tsx")).unwrap().components
+    }
+
+    #[test]
+    fn records_props_defaults_hooks_wrappers_and_children() {
+        let src = r#"
+import React, { memo, forwardRef, useState } from 'react';
+import { useQuery } from './api';
+interface ButtonProps extends Base { label: string; size?: 'sm' | 'lg'; onClick(e: Event): void }
+export function Button({ label, size = 'sm' }: ButtonProps) {
+  const [open, setOpen] = useState(false);
+  const data = React.useMemo(() => useQuery(label), [label]);
+  return <Form.Field><Icon />{open && <span><Icon /></span>}</Form.Field>;
+}
+export const Input = memo(forwardRef<HTMLInputElement, { value: string; disabled?: boolean }>(
+  ({ value, disabled = false }, ref) => <input ref={ref} value={value} />
+));
+export const Card: React.FC<CardProps> = ({ title }) => <Button label={title} />;
+export const Panel: Readonly<PanelOptions> = () => <div />;
+export const helper = () => 1;
+const notAComponent = makeThing();
+"#;
+        let found = components(src);
+        assert_eq!(
+            found.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            ["Button", "Input", "Card", "Panel"]
+        );
+
+        let button = &found[0];
+        let props = button.props.as_ref().unwrap();
+        assert_eq!(props.type_name.as_deref(), Some("ButtonProps"));
+        assert_eq!(props.extends, ["Base"]);
+        let fields: Vec<(&str, &str, bool)> = props
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.ty.as_str(), f.optional))
+            .collect();
+        assert_eq!(
+            fields,
+            [
+                ("label", "string", false),
+                ("size", "'sm' | 'lg'", true),
+                ("onClick", "(e: Event): void", false)
+            ]
+        );
+        assert_eq!(button.default_props.get("size").map(String::as_str), Some("'sm'"));
+        assert_eq!(button.hooks, ["useState", "useMemo", "useQuery"]);
+        assert_eq!(button.renders, ["Form.Field", "Icon"]);
+        assert_eq!(button.location.start_line, 5);
+
+        let input = &found[1];
+        assert_eq!(input.wrappers, ["memo", "forwardRef"]);
+        let props = input.props.as_ref().unwrap();
+        assert_eq!(props.type_name, None);
+        assert_eq!(props.fields.len(), 2);
+        assert_eq!(input.default_props.get("disabled").map(String::as_str), Some("false"));
+        assert!(input.renders.is_empty());
+
+        let card = &found[2];
+        let props = card.props.as_ref().unwrap();
+        assert_eq!(props.type_name.as_deref(), Some("CardProps"));
+        assert!(props.fields.is_empty(), "declared elsewhere, resolved at query time");
+        assert_eq!(card.renders, ["Button"]);
+        assert!(found[3].props.is_none(), "only FC annotations name the props type");
+    }
+
+    #[test]
+    fn wrapped_components_get_the_component_kind() {
+        let ir = skeletonize_source(
+            "export const List = React.memo(function List() { return null; });\n",
+            Path::new("List.tsx"),
+        )
+        .unwrap();
+        assert_eq!(ir.symbols[0].kind, "component");
+        let ts = skeletonize_source("export const List = memo(() => null);\n", Path::new("List.ts")).unwrap();
+        assert!(ts.components.is_empty());
+    }
+}
@@ -0,0 +1,317 @@
+//! Structural test reachability.
+//!
+//! Test files are graph keys matching configurable globs. A source file is
+//! reached by a test when it is in the test's transitive `dependencies`; an
+//! exported declaration is reached when a test, or a file the test reaches,
+//! imports it by name or through a namespace import, with barrels followed
+//! back to the declaration. Re-exports alone do not count. This measures
+//! "loaded by a test", not "asserted on".
+
+use ignore::gitignore::Gitignore;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use crate::graph::{key_matches, AppState};
+use crate::skeleton::{FileSkeleton, Location};
+use crate::symbols::{resolve_export, ExportResolution};
+
+/// `test/` and `tests/` are anchored to the root so source directories that
+/// happen to share the name (`src/parser/test/fixtures.ts`) stay source.
+pub const DEFAULT_TEST_GLOBS: &[&str] = &["*.test.*", "*.spec.*", "__tests__/", "/test/", "/tests/"];
+
+/// A test reaching a file, `depth` imports away (1 = imported directly).
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CoveringTest {
+    pub file: String,
+    pub depth: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ExportReach {
+    pub name: String,
+    pub kind: String,
+    pub location: Location,
+    /// Tests whose import chains consume this export.
+    pub tests: Vec<String>,
+    /// A test file imports it itself, not only through other source files.
+    pub direct: bool,
+}
+
+/// Which tests reach one file, and which of its exports they consume.
+#[derive(Serialize, Debug, Clone)]
+pub struct FileReach {
+    pub file: String,
+    pub is_test: bool,
+    pub tests: Vec<CoveringTest>,
+    pub exports: Vec<ExportReach>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FileSummary {
+    pub file: String,
+    /// Number of tests reaching the file.
+    pub tests: usize,
+    pub exports: usize,
+    pub untested_exports: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Summary {
+    pub test_files: Vec<String>,
+    /// Non-test files, untested ones first, then by fewest reaching tests.
+    pub files: Vec<FileSummary>,
+}
+
+pub struct TestMap {
+    tests: Vec<String>,
+    /// file -> test -> shortest import distance.
+    files: HashMap<String, BTreeMap<String, usize>>,
+    /// (declaring file, symbol) -> test -> consumed by the test itself.
+    symbols: HashMap<(String, String), BTreeMap<String, bool>>,
+}
+
+/// Every declaration `key` exports, following `export *` and named
+/// re-exports.
+fn exported_declarations(
+    state: &AppState,
+    key: &str,
+    visited: &mut HashSet<String>,
+    out: &mut Vec<(String, String)>,
+) {
+    if !visited.insert(key.to_string()) {
+        return;
+    }
+    let Some(node) = state.skeleton_graph.get(key).map(|n| n.clone()) else {
+        return;
+    };
+    for entry in &node.export_table {
+        if entry.name == "*" {
+            let target = node
+                .import_records
+                .iter()
+                .find(|r| Some(r.source.as_str()) == entry.source.as_deref())
+                .and_then(|r| r.resolved.clone());
+            if let Some(target) = target {
+                exported_declarations(state, &target, visited, out);
+            }
+        } else if let ExportResolution::Defined(def) = resolve_export(state, key, &entry.name) {
+            out.push((def.file, def.symbol.name));
+        }
+    }
+}
+
+/// A file's consumed declarations and its dependencies.
+type Reached = (Vec<(String, String)>, Vec<String>);
+
+/// Declarations a file consumes through its imports; re-exports excluded.
+fn consumed(state: &AppState, node: &FileSkeleton) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    for record in &node.import_records {
+        let Some(target) = &record.resolved else {
+            continue;
+        };
+        for binding in &record.bindings {
+            let reexport = node.export_table.iter().any(|e| {
+                e.source.as_deref() == Some(record.source.as_str())
+                    && e.imported.as_deref() == Some(binding.imported.as_str())
+                    && e.name == binding.local
+            });
+            if reexport {
+                continue;
+            }
+            if binding.imported == "*" {
+                exported_declarations(state, target, &mut HashSet::new(), &mut out);
+            } else if let ExportResolution::Defined(def) =
+                resolve_export(state, target, &binding.imported)
+            {
+                out.push((def.file, def.symbol.name));
+            }
+        }
+    }
+    out
+}
+
+impl TestMap {
+    pub fn build(state: &AppState, test_globs: &Gitignore) -> TestMap {
+        let mut tests: Vec<String> = state
+            .skeleton_graph
+            .iter()
+            .map(|e| e.key().clone())
+            .filter(|k| key_matches(test_globs, k))
+            .collect();
+        tests.sort();
+
+        let mut files: HashMap<String, BTreeMap<String, usize>> = HashMap::new();
+        let mut symbols: HashMap<(String, String), BTreeMap<String, bool>> = HashMap::new();
+        // What each file consumes and depends on, computed the first time any
+        // test reaches it: shared helpers are reached by most tests.
+        let mut visited: HashMap<String, Reached> = HashMap::new();
+        for test in &tests {
+            let mut seen = HashSet::from([test.clone()]);
+            let mut queue = VecDeque::from([(test.clone(), 0)]);
+            while let Some((file, depth)) = queue.pop_front() {
+                if !visited.contains_key(&file) {
+                    let Some(node) = state.skeleton_graph.get(&file) else {
+                        continue;
+                    };
+                    let entry = (consumed(state, &node), node.dependencies.clone());
+                    drop(node);
+                    visited.insert(file.clone(), entry);
+                }
+                let (decls, dependencies) = &visited[&file];
+                for decl in decls {
+                    let direct = symbols.entry(decl.clone()).or_default().entry(test.clone()).or_insert(false);
+                    *direct |= depth == 0;
+                }
+                for dep in dependencies {
+                    if seen.insert(dep.clone()) {
+                        files.entry(dep.clone()).or_default().insert(test.clone(), depth + 1);
+                        queue.push_back((dep.clone(), depth + 1));
+                    }
+                }
+            }
+        }
+        TestMap {
+            tests,
+            files,
+            symbols,
+        }
+    }
+
+    fn is_test(&self, key: &str) -> bool {
+        self.tests.binary_search_by(|t| t.as_str().cmp(key)).is_ok()
+    }
+
+    /// Reachability of one file and each declaration it exports.
+    pub fn file(&self, state: &AppState, key: &str) -> FileReach {
+        let mut tests: Vec<CoveringTest> = self
+            .files
+            .get(key)
+            .into_iter()
+            .flatten()
+            .map(|(file, depth)| CoveringTest {
+                file: file.clone(),
+                depth: *depth,
+            })
+            .collect();
+        tests.sort_by(|a, b| (a.depth, &a.file).cmp(&(b.depth, &b.file)));
+
+        let mut exports = Vec::new();
+        if let Some(node) = state.skeleton_graph.get(key).map(|n| n.clone()) {
+            for entry in node
+                .export_table
+                .iter()
+                .filter(|e| e.source.is_none() && e.name != "*")
+            {
+                let ExportResolution::Defined(def) = resolve_export(state, key, &entry.name) else {
+                    continue;
+                };
+                if def.file != key {
+                    continue;
+                }
+                let reached = self.symbols.get(&(def.file, def.symbol.name.clone()));
+                exports.push(ExportReach {
+                    name: entry.name.clone(),
+                    kind: def.symbol.kind.clone(),
+                    location: def.symbol.location,
+                    tests: reached.into_iter().flatten().map(|(t, _)| t.clone()).collect(),
+                    direct: reached.is_some_and(|r| r.values().any(|d| *d)),
+                });
+            }
+        }
+        FileReach {
+            file: key.to_string(),
+            is_test: self.is_test(key),
+            tests,
+            exports,
+        }
+    }
+
+    /// Per-file counts for every non-test file in the graph.
+    pub fn summary(&self, state: &AppState) -> Summary {
+        let mut keys: Vec<String> = state
+            .skeleton_graph
+            .iter()
+            .map(|e| e.key().clone())
+            .filter(|k| !self.is_test(k))
+            .collect();
+        keys.sort();
+        let mut files: Vec<FileSummary> = keys
+            .iter()
+            .map(|key| {
+                let reach = self.file(state, key);
+                FileSummary {
+                    file: reach.file,
+                    tests: reach.tests.len(),
+                    exports: reach.exports.len(),
+                    untested_exports: reach
+                        .exports
+                        .into_iter()
+                        .filter(|e| e.tests.is_empty())
+                        .map(|e| e.name)
+                        .collect(),
+                }
+            })
+            .collect();
+        files.sort_by_key(|f| f.tests);
+        Summary {
+            test_files: self.tests.clone(),
+            files,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::glob_matcher;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn tests_reach_files_transitively_and_exports_by_name() {
+        let (_dir, state) = project(&[
+            ("src/utils.ts", "export const used = 1;\nexport const indirect = 2;\nexport const unused = 3;\n"),
+            ("src/index.ts", "export * from './utils';\nexport * from './other';\n"),
+            ("src/other.ts", "export const skipped = 1;\n"),
+            ("src/service.ts", "import { indirect } from './utils';\nexport function run() {}\n"),
+            ("src/lonely.ts", "export const alone = 1;\n"),
+            ("src/service.test.ts", "import { used } from './index';\nimport * as s from './service';\n"),
+            ("src/__tests__/deep.ts", "import { run } from '../service';\n"),
+            ("src/parser/test/fixtures.ts", "export const sample = 1;\n"),
+            ("tests/e2e.ts", "import { alone } from '../src/lonely';\n"),
+        ]);
+        let globs: Vec<String> = DEFAULT_TEST_GLOBS.iter().map(|g| g.to_string()).collect();
+        let map = TestMap::build(&state, &glob_matcher(&state.root, &globs).unwrap());
+
+        let utils = map.file(&state, "src/utils.ts");
+        assert!(!utils.is_test);
+        assert_eq!(
+            utils.tests,
+            vec![
+                CoveringTest { file: "src/__tests__/deep.ts".into(), depth: 2 },
+                CoveringTest { file: "src/service.test.ts".into(), depth: 2 },
+            ]
+        );
+        let exports: Vec<(&str, Vec<String>, bool)> = utils
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.tests.clone(), e.direct))
+            .collect();
+        assert_eq!(
+            exports,
+            vec![
+                ("used", vec!["src/service.test.ts".to_string()], true),
+                ("indirect", vec!["src/__tests__/deep.ts".to_string(), "src/service.test.ts".to_string()], false),
+                ("unused", vec![], false),
+            ]
+        );
+        assert!(map.file(&state, "src/service.test.ts").is_test);
+
+        let summary = map.summary(&state);
+        assert_eq!(summary.test_files, ["src/__tests__/deep.ts", "src/service.test.ts", "tests/e2e.ts"]);
+        let fixtures = &summary.files[0];
+        assert_eq!((fixtures.file.as_str(), fixtures.tests), ("src/parser/test/fixtures.ts", 0));
+        let other = summary.files.iter().find(|f| f.file == "src/other.ts").unwrap();
+        assert_eq!(other.untested_exports, ["skipped"], "a barrel's export * is not consumption");
+    }
+}
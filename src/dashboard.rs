@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Json as AxumJson, State as AxumState},
+    extract::{Json as AxumJson, Query, State as AxumState},
     response::{Html, IntoResponse},
     routing::get,
     Router,
@@ -10,6 +10,7 @@ use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::churn;
 use crate::graph::{perform_initial_sweep, AppState, LogEntry};
 
 // --- WEB DASHBOARD ---
@@ -527,6 +528,18 @@ async fn get_status(AxumState(state): AxumState<Arc<AppState>>) -> impl IntoResp
     })
 }
 
+async fn get_hotspots(
+    AxumState(state): AxumState<Arc<AppState>>,
+    Query(opts): Query<churn::Options>,
+) -> impl IntoResponse {
+    let report = tokio::task::spawn_blocking(move || churn::report(&state, &opts)).await;
+    match report {
+        Ok(Ok(report)) => AxumJson(json!(report)),
+        Ok(Err(e)) => AxumJson(json!({"error": e.to_string()})),
+        Err(e) => AxumJson(json!({"error": format!("task failed: {}", e)})),
+    }
+}
+
 async fn control_server(
     AxumState(state): AxumState<Arc<AppState>>,
     AxumJson(req): AxumJson<ControlRequest>,
@@ -572,6 +585,7 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/api/skeletons", get(list_skeletons).delete(delete_skeleton))
         .route("/api/logs", get(list_logs))
         .route("/api/status", get(get_status))
+        .route("/api/hotspots", get(get_hotspots))
         .route("/api/control", axum::routing::post(control_server))
         .with_state(state)
 }
@@ -0,0 +1,402 @@
+//! Read-only access to file contents at a git revision.
+//!
+//! Blobs are read from the local repository with the `git` binary; nothing
+//! here touches the working tree, the index or the live graph.
+
+use anyhow::{bail, Context, Result};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Rejects revisions that could be taken as options or that would break the
+/// `rev:path` blob syntax.
+fn check_rev(rev: &str) -> Result<()> {
+    if rev.is_empty()
+        || rev.starts_with('-')
+        || rev.contains(':')
+        || rev.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        bail!("invalid revision '{}'", rev);
+    }
+    Ok(())
+}
+
+/// Contents of the graph key `key` (relative to `root`) as of `rev`.
+pub fn show_file(root: &Path, rev: &str, key: &str) -> Result<String> {
+    check_rev(rev)?;
+    // `./` makes the path relative to `root` even when it is a subdirectory
+    // of the repository.
+    let stdout = git_output(root, &["cat-file", "blob", &format!("{}:./{}", rev, key)])?;
+    String::from_utf8(stdout).context("file is not valid UTF-8")
+}
+
+/// `git -C root <args>` with prompts disabled; stdout on success.
+fn git_output(root: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(output.stdout)
+}
+
+/// `root`'s path inside its repository, `/`-terminated (empty at the
+/// top level).
+pub fn prefix(root: &Path) -> Result<String> {
+    let stdout = git_output(root, &["rev-parse", "--show-prefix"])?;
+    Ok(String::from_utf8_lossy(&stdout).trim_end_matches('\n').to_string())
+}
+
+/// Every file under `root` as of `rev`, as root-relative keys.
+pub fn list_files(root: &Path, rev: &str) -> Result<Vec<String>> {
+    check_rev(rev)?;
+    // Run from `root`, ls-tree lists only that subtree, relative to it.
+    let stdout = git_output(root, &["ls-tree", "-r", "-z", "--name-only", rev])?;
+    Ok(stdout
+        .split(|b| *b == 0)
+        .filter(|p| !p.is_empty())
+        .map(|p| String::from_utf8_lossy(p).into_owned())
+        .collect())
+}
+
+/// Contents of several keys as of `rev` through one `git cat-file --batch`.
+/// Missing and non-UTF-8 blobs come back as `None`.
+pub fn show_files(root: &Path, rev: &str, keys: &[String]) -> Result<Vec<(String, Option<String>)>> {
+    check_rev(rev)?;
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["cat-file", "--batch"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("failed to run git")?;
+    let mut stdin = child.stdin.take().context("git stdin unavailable")?;
+    // Fed from a thread so a full stdout pipe can't deadlock the writes.
+    let requests: Vec<String> = keys.iter().map(|k| format!("{}:./{}\n", rev, k)).collect();
+    let writer = std::thread::spawn(move || {
+        for request in requests {
+            if stdin.write_all(request.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+    let mut stdout = BufReader::new(child.stdout.take().context("git stdout unavailable")?);
+    let mut out = Vec::with_capacity(keys.len());
+    for key in keys {
+        let mut header = String::new();
+        stdout.read_line(&mut header)?;
+        // `<oid> <type> <size>` followed by the object, or `<object> missing`.
+        let mut fields = header.split_whitespace().skip(1);
+        let (kind, size) = match (fields.next(), fields.next().and_then(|s| s.parse::<usize>().ok())) {
+            (Some(kind), Some(size)) => (kind, size),
+            _ => {
+                out.push((key.clone(), None));
+                continue;
+            }
+        };
+        // Trees and gitlinked commits still carry a body; consume it so the
+        // next header lines up.
+        let mut content = vec![0; size + 1];
+        stdout.read_exact(&mut content)?;
+        content.pop();
+        let text = if kind == "blob" { String::from_utf8(content).ok() } else { None };
+        out.push((key.clone(), text));
+    }
+    let _ = writer.join();
+    let _ = child.wait();
+    Ok(out)
+}
+
+/// A changed file in a diff, with the new-side line ranges its hunks touch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedFile {
+    /// Root-relative path.
+    pub path: String,
+    /// added | modified | deleted | untracked
+    pub status: String,
+    /// Inclusive 1-based new-side line ranges. A pure deletion between lines
+    /// `n` and `n + 1` is `(n, n + 1)`. Empty for untracked and deleted
+    /// files, whose every line counts as changed.
+    pub hunks: Vec<(u32, u32)>,
+}
+
+/// The first of the usual default branches that exists.
+pub fn default_base(root: &Path) -> Result<String> {
+    for candidate in ["origin/HEAD", "origin/main", "main", "origin/master", "master"] {
+        let spec = format!("{}^{{commit}}", candidate);
+        if git_output(root, &["rev-parse", "--verify", "--quiet", &spec]).is_ok() {
+            return Ok(candidate.to_string());
+        }
+    }
+    bail!("no default base branch found (tried origin/HEAD, main, master); pass one explicitly")
+}
+
+pub fn merge_base(root: &Path, base: &str) -> Result<String> {
+    check_rev(base)?;
+    let stdout = git_output(root, &["merge-base", base, "HEAD"])?;
+    Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+/// Path from a `--- a/x` / `+++ b/x` header, unquoting git's C-style quoting
+/// for the common cases.
+fn header_path(rest: &str) -> Option<String> {
+    if rest == "/dev/null" {
+        return None;
+    }
+    let unquoted = match rest.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => rest.to_string(),
+    };
+    Some(unquoted.split_once('/').map(|(_, p)| p.to_string()).unwrap_or(unquoted))
+}
+
+/// `+c,d` of a hunk header as an inclusive line range.
+fn hunk_range(header: &str) -> Option<(u32, u32)> {
+    let new = header.split(' ').find(|p| p.starts_with('+'))?.trim_start_matches('+');
+    let (start, count) = match new.split_once(',') {
+        Some((s, c)) => (s.parse::<u32>().ok()?, c.parse::<u32>().ok()?),
+        None => (new.parse::<u32>().ok()?, 1),
+    };
+    Some(if count == 0 { (start, start + 1) } else { (start, start + count - 1) })
+}
+
+/// Files under `root` changed between `rev` and the working tree (committed,
+/// staged and unstaged edits alike), plus untracked files. Renames are
+/// reported as a deletion and an addition.
+pub fn changed_files(root: &Path, rev: &str) -> Result<Vec<ChangedFile>> {
+    check_rev(rev)?;
+    let stdout = git_output(
+        root,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--no-renames",
+            "--relative",
+            "-U0",
+            rev,
+        ],
+    )?;
+    let mut files: Vec<ChangedFile> = Vec::new();
+    let (mut old_path, mut status) = (None, "modified");
+    // Header lines are only parsed before a file's first hunk, so removed
+    // content starting with `-- ` is never taken for one.
+    let mut in_header = false;
+    for line in String::from_utf8_lossy(&stdout).lines() {
+        if line.starts_with("diff --git ") {
+            (status, in_header) = ("modified", true);
+        } else if !in_header {
+            if line.starts_with("@@ ")
+                && status != "deleted"
+                && let (Some(file), Some(range)) = (files.last_mut(), hunk_range(line))
+            {
+                file.hunks.push(range);
+            }
+        } else if line.starts_with("new file mode") {
+            status = "added";
+        } else if line.starts_with("deleted file mode") {
+            status = "deleted";
+        } else if let Some(rest) = line.strip_prefix("--- ") {
+            old_path = header_path(rest);
+        } else if let Some(rest) = line.strip_prefix("+++ ") {
+            in_header = false;
+            if let Some(path) = header_path(rest).or_else(|| old_path.take()) {
+                files.push(ChangedFile {
+                    path,
+                    status: status.to_string(),
+                    hunks: Vec::new(),
+                });
+            }
+        }
+    }
+    let untracked = git_output(root, &["ls-files", "-z", "--others", "--exclude-standard"])?;
+    files.extend(
+        untracked
+            .split(|b| *b == 0)
+            .filter(|p| !p.is_empty())
+            .map(|p| ChangedFile {
+                path: String::from_utf8_lossy(p).into_owned(),
+                status: "untracked".to_string(),
+                hunks: Vec::new(),
+            }),
+    );
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// One non-merge commit and the files under `root` it touched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    pub hash: String,
+    /// Committer time, seconds since the epoch.
+    pub timestamp: i64,
+    pub files: Vec<String>,
+}
+
+/// Up to `max_commits` non-merge commits reachable from `HEAD` that touch
+/// files under `root`, newest first, optionally only those after `since`
+/// (any date `git log --since` accepts: `2024-01-01`, `6 months ago`).
+pub fn log(root: &Path, max_commits: usize, since: Option<&str>) -> Result<Vec<Commit>> {
+    let mut args = vec![
+        "-c".to_string(),
+        "core.quotePath=false".to_string(),
+        "log".to_string(),
+        "--no-merges".to_string(),
+        "--no-renames".to_string(),
+        "--relative".to_string(),
+        "--name-only".to_string(),
+        "--format=%x00%H %ct".to_string(),
+        format!("--max-count={}", max_commits),
+    ];
+    if let Some(since) = since {
+        args.push(format!("--since={}", since));
+    }
+    args.extend(["HEAD".to_string(), "--".to_string(), ".".to_string()]);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // A repository without commits has no history to walk.
+    if git_output(root, &["rev-parse", "--verify", "--quiet", "HEAD"]).is_err() {
+        git_output(root, &["rev-parse", "--git-dir"])?;
+        return Ok(Vec::new());
+    }
+    let stdout = git_output(root, &args)?;
+    let text = String::from_utf8_lossy(&stdout);
+    Ok(text
+        .split('\0')
+        .filter_map(|entry| {
+            let mut lines = entry.lines();
+            let (hash, timestamp) = lines.next()?.split_once(' ')?;
+            Some(Commit {
+                hash: hash.to_string(),
+                timestamp: timestamp.parse().ok()?,
+                files: lines.filter(|l| !l.is_empty()).map(str::to_string).collect(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "user.name=t", "-c", "user.email=t@t", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn reads_blobs_at_a_revision_relative_to_a_subdirectory_root() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        std::fs::create_dir_all(repo.join("web/src")).unwrap();
+        std::fs::write(repo.join("web/src/api.ts"), "export const v = 1;\n").unwrap();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "one"]);
+        std::fs::write(repo.join("web/src/api.ts"), "export const v = 2;\n").unwrap();
+        git(repo, &["commit", "-q", "-am", "two"]);
+
+        let root = repo.join("web");
+        assert_eq!(prefix(&root).unwrap(), "web/");
+        assert_eq!(prefix(repo).unwrap(), "");
+        assert_eq!(show_file(&root, "main~1", "src/api.ts").unwrap(), "export const v = 1;\n");
+        assert_eq!(show_file(&root, "HEAD", "src/api.ts").unwrap(), "export const v = 2;\n");
+        assert!(show_file(&root, "main", "src/missing.ts").is_err());
+        assert!(show_file(&root, "--output=/tmp/x", "src/api.ts").is_err());
+        assert!(show_file(&root, "main:src", "api.ts").is_err());
+
+        std::fs::write(repo.join("top.ts"), "").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "three"]);
+        assert_eq!(list_files(&root, "HEAD").unwrap(), ["src/api.ts"]);
+        let keys = vec!["src".to_string(), "src/api.ts".to_string(), "src/missing.ts".to_string()];
+        assert_eq!(
+            show_files(&root, "main~2", &keys).unwrap(),
+            vec![
+                ("src".to_string(), None),
+                ("src/api.ts".to_string(), Some("export const v = 1;\n".to_string())),
+                ("src/missing.ts".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_files_cover_commits_edits_and_untracked_files_since_the_merge_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        std::fs::create_dir_all(repo.join("web/src")).unwrap();
+        std::fs::write(repo.join("web/src/a.ts"), "one\ntwo\nthree\nfour\n").unwrap();
+        std::fs::write(repo.join("web/src/gone.ts"), "x\n").unwrap();
+        std::fs::write(repo.join("outside.ts"), "x\n").unwrap();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "base"]);
+        git(repo, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(repo.join("web/src/a.ts"), "one\nTWO\nthree\n").unwrap();
+        std::fs::remove_file(repo.join("web/src/gone.ts")).unwrap();
+        git(repo, &["commit", "-q", "-am", "edit"]);
+        std::fs::write(repo.join("web/src/new.ts"), "-- not a header\n").unwrap();
+        std::fs::write(repo.join("outside.ts"), "y\n").unwrap();
+
+        let root = repo.join("web");
+        let base = merge_base(&root, "main").unwrap();
+        assert_eq!(default_base(&root).unwrap(), "main");
+        let changed = changed_files(&root, &base).unwrap();
+        let summary: Vec<_> = changed
+            .iter()
+            .map(|f| (f.path.as_str(), f.status.as_str(), f.hunks.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/a.ts", "modified", vec![(2, 2), (3, 4)]),
+                ("src/gone.ts", "deleted", vec![]),
+                ("src/new.ts", "untracked", vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn log_lists_commits_touching_the_root_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        std::fs::create_dir_all(repo.join("web/src")).unwrap();
+        std::fs::write(repo.join("web/src/a.ts"), "1").unwrap();
+        std::fs::write(repo.join("web/src/b.ts"), "1").unwrap();
+        git(repo, &["init", "-q", "-b", "main"]);
+        let root = repo.join("web");
+        assert!(log(&root, 10, None).unwrap().is_empty());
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "one"]);
+        std::fs::write(repo.join("outside.ts"), "1").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "outside only"]);
+        std::fs::write(repo.join("web/src/a.ts"), "2").unwrap();
+        git(repo, &["commit", "-q", "-am", "two"]);
+
+        let commits = log(&root, 10, None).unwrap();
+        let files: Vec<Vec<String>> = commits.iter().map(|c| c.files.clone()).collect();
+        assert_eq!(files, vec![vec!["src/a.ts".to_string()], vec!["src/a.ts".to_string(), "src/b.ts".to_string()]]);
+        assert!(commits[0].timestamp > 0 && commits[0].hash.len() == 40);
+        assert_eq!(log(&root, 1, None).unwrap().len(), 1);
+        assert!(log(&root, 10, Some("2090-01-01")).unwrap().is_empty());
+    }
+}
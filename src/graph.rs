@@ -3,7 +3,7 @@ use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use ignore::WalkBuilder;
 use serde::Serialize;
 use serde_json::Value;
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::{Component, Path, PathBuf};
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, RwLock};
@@ -11,6 +11,7 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use std::collections::HashSet;
 
+use crate::architecture::{Architecture, Violation};
 use crate::resolve::{FsResolver, Resolution, Resolver};
 use crate::skeleton::{skeletonize_file, FileSkeleton};
 
@@ -65,6 +66,23 @@ pub fn is_skeleton_target(path: &Path) -> bool {
     )
 }
 
+/// Compile gitignore-style globs (`src/pages/**`, `*.stories.tsx`) into a
+/// matcher for root-relative graph keys.
+pub fn glob_matcher(root: &Path, globs: &[String]) -> anyhow::Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new(root);
+    for glob in globs {
+        builder.add_line(None, glob)?;
+    }
+    Ok(builder.build()?)
+}
+
+/// True if `key` (or one of its parent directories) matches `matcher`.
+pub fn key_matches(matcher: &Gitignore, key: &str) -> bool {
+    matcher
+        .matched_path_or_any_parents(Path::new(key), false)
+        .is_ignore()
+}
+
 #[derive(Serialize, Clone)]
 pub struct LogEntry {
     pub timestamp: u64,
@@ -79,6 +97,16 @@ pub struct AppState {
     pub skeleton_graph: DashMap<String, FileSkeleton>,
     /// Reverse dependency index: key -> set of files importing it.
     pub dependents: DashMap<String, HashSet<String>>,
+    /// Symbol-level reverse index: (key, export name) -> files consuming that
+    /// export directly. Namespace imports and `export *` are filed under `*`.
+    pub symbol_dependents: DashMap<(String, String), HashSet<String>>,
+    /// Rules from `.skeletonizer.json` and the violations they currently
+    /// flag, per importing file.
+    pub architecture: RwLock<Architecture>,
+    pub violations: DashMap<String, Vec<Violation>>,
+    /// Held for write while a watcher batch is applied; whole-graph readers
+    /// hold it for read so they never observe a half-applied branch switch.
+    pub batch_gate: RwLock<()>,
     /// Resource URIs the client subscribed to via resources/subscribe.
     pub subscriptions: RwLock<HashSet<String>>,
     pub logs: RwLock<VecDeque<LogEntry>>,
@@ -99,6 +127,10 @@ impl AppState {
             resolver: Box::new(FsResolver),
             skeleton_graph: DashMap::new(),
             dependents: DashMap::new(),
+            symbol_dependents: DashMap::new(),
+            architecture: RwLock::new(Architecture::default()),
+            violations: DashMap::new(),
+            batch_gate: RwLock::new(()),
             subscriptions: RwLock::new(HashSet::new()),
             logs: RwLock::new(VecDeque::new()),
             uptime_acc: RwLock::new(Duration::ZERO),
@@ -141,9 +173,18 @@ impl AppState {
     pub fn upsert(&self, key: String, mut skeleton: FileSkeleton) -> bool {
         let mut deps = Vec::new();
         let mut externals = Vec::new();
-        for record in &skeleton.import_records {
+        let mut consumed: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for record in &mut skeleton.import_records {
+            record.resolved = None;
             match self.resolver.resolve(&self.root, &key, &record.source) {
-                Resolution::Internal(k) if k != key => deps.push(k),
+                Resolution::Internal(k) if k != key => {
+                    let names = consumed.entry(k.clone()).or_default();
+                    names.extend(record.bindings.iter().map(|b| b.imported.clone()));
+                    names.sort();
+                    names.dedup();
+                    record.resolved = Some(k.clone());
+                    deps.push(k);
+                }
                 Resolution::External(pkg) => externals.push(pkg),
                 _ => {}
             }
@@ -154,6 +195,7 @@ impl AppState {
         externals.dedup();
         skeleton.dependencies = deps.clone();
         skeleton.external_deps = externals;
+        skeleton.symbol_dependencies = consumed.clone();
 
         let old = self.skeleton_graph.insert(key.clone(), skeleton);
         if let Some(old) = &old {
@@ -162,6 +204,7 @@ impl AppState {
                     set.remove(&key);
                 }
             }
+            self.unlink_symbols(&key, &old.symbol_dependencies);
         }
         for dep in &deps {
             self.dependents
@@ -169,9 +212,29 @@ impl AppState {
                 .or_default()
                 .insert(key.clone());
         }
+        for (dep, names) in &consumed {
+            for name in names {
+                self.symbol_dependents
+                    .entry((dep.clone(), name.clone()))
+                    .or_default()
+                    .insert(key.clone());
+            }
+        }
         old.is_none()
     }
 
+    fn unlink_symbols(&self, key: &str, edges: &BTreeMap<String, Vec<String>>) {
+        for (dep, names) in edges {
+            for name in names {
+                let slot = (dep.clone(), name.clone());
+                if let Some(mut set) = self.symbol_dependents.get_mut(&slot) {
+                    set.remove(key);
+                }
+                self.symbol_dependents.remove_if(&slot, |_, set| set.is_empty());
+            }
+        }
+    }
+
     /// Remove a node and its outgoing edges from the reverse-dependency
     /// index. Returns `true` when it existed.
     pub fn remove(&self, key: &str) -> bool {
@@ -182,12 +245,61 @@ impl AppState {
                         set.remove(key);
                     }
                 }
+                self.unlink_symbols(key, &old.symbol_dependencies);
                 true
             }
             None => false,
         }
     }
 
+    /// Apply a batch of upserts and removals as one unit under `batch_gate`.
+    /// Returns the `(added, updated, removed)` keys.
+    pub fn apply_batch(
+        &self,
+        upserts: Vec<(String, FileSkeleton)>,
+        removals: Vec<String>,
+    ) -> (Vec<String>, Vec<String>, Vec<String>) {
+        let _gate = self.batch_gate.write().unwrap();
+        let (mut added, mut updated, mut removed) = (Vec::new(), Vec::new(), Vec::new());
+        for (key, skeleton) in upserts {
+            if self.upsert(key.clone(), skeleton) {
+                added.push(key);
+            } else {
+                updated.push(key);
+            }
+        }
+        for key in removals {
+            if self.remove(&key) {
+                removed.push(key);
+            }
+        }
+        (added, updated, removed)
+    }
+
+    /// Re-run import resolution for every node after a resolver config file
+    /// changed. Returns the keys whose resolved edges changed.
+    pub fn reresolve_all(&self) -> Vec<String> {
+        self.resolver.invalidate();
+        let _gate = self.batch_gate.write().unwrap();
+        let nodes: Vec<(String, FileSkeleton)> = self
+            .skeleton_graph
+            .iter()
+            .map(|e| (e.key().clone(), e.value().clone()))
+            .collect();
+        let mut changed = Vec::new();
+        for (key, skeleton) in nodes {
+            let before = (skeleton.dependencies.clone(), skeleton.external_deps.clone());
+            self.upsert(key.clone(), skeleton);
+            if let Some(node) = self.skeleton_graph.get(&key)
+                && (node.dependencies != before.0 || node.external_deps != before.1)
+            {
+                changed.push(key);
+            }
+        }
+        changed.sort();
+        changed
+    }
+
     /// Files that import `key`, from the reverse index.
     pub fn dependents_of(&self, key: &str) -> Vec<String> {
         let mut v: Vec<String> = self
@@ -199,6 +311,18 @@ impl AppState {
         v
     }
 
+    /// Files consuming export `name` of `key` directly (not through barrels),
+    /// from the symbol-level reverse index.
+    pub fn symbol_dependents_of(&self, key: &str, name: &str) -> Vec<String> {
+        let mut v: Vec<String> = self
+            .symbol_dependents
+            .get(&(key.to_string(), name.to_string()))
+            .map(|s| s.iter().cloned().collect())
+            .unwrap_or_default();
+        v.sort();
+        v
+    }
+
     pub fn add_log(&self, direction: &str, payload: Value) {
         let mut logs = self.logs.write().unwrap();
         if logs.len() >= 200 {
@@ -215,6 +339,124 @@ impl AppState {
     }
 }
 
+/// Import cycles: strongly connected components of the dependency graph
+/// with more than one file, each sorted, in key order. Iterative Tarjan, so
+/// long import chains can't overflow the stack.
+pub fn import_cycles(state: &AppState) -> Vec<Vec<String>> {
+    let mut keys: Vec<String> = state.skeleton_graph.iter().map(|e| e.key().clone()).collect();
+    keys.sort();
+    let ids: HashMap<&str, usize> = keys.iter().enumerate().map(|(i, k)| (k.as_str(), i)).collect();
+    let edges: Vec<Vec<usize>> = keys
+        .iter()
+        .map(|k| {
+            state
+                .skeleton_graph
+                .get(k)
+                .map(|n| n.dependencies.iter().filter_map(|d| ids.get(d.as_str()).copied()).collect())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let n = keys.len();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut next = 0;
+    let mut components = Vec::new();
+    for start in 0..n {
+        if index[start] != usize::MAX {
+            continue;
+        }
+        // (node, position in its edge list)
+        let mut frames = vec![(start, 0)];
+        index[start] = next;
+        low[start] = next;
+        next += 1;
+        stack.push(start);
+        on_stack[start] = true;
+        while let Some(&mut (v, ref mut pos)) = frames.last_mut() {
+            if let Some(&w) = edges[v].get(*pos) {
+                *pos += 1;
+                if index[w] == usize::MAX {
+                    index[w] = next;
+                    low[w] = next;
+                    next += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    frames.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(index[w]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[v]);
+            }
+            if low[v] == index[v] {
+                let mut component = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack[w] = false;
+                    component.push(keys[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                if component.len() > 1 {
+                    component.sort();
+                    components.push(component);
+                }
+            }
+        }
+    }
+    components.sort();
+    components
+}
+
+/// Every tracked .ts/.tsx file under `dir` (normally the root) as
+/// `(key, absolute path)`. `.git/` and `node_modules/` are pruned during
+/// descent — on large or slow filesystems, even visiting their entries is
+/// expensive.
+pub fn walk_targets(state: &AppState, dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut out = Vec::new();
+    let walker = WalkBuilder::new(dir)
+        .filter_entry(|e| {
+            let name = e.file_name().to_str().unwrap_or("");
+            name != ".git" && name != "node_modules"
+        })
+        .build();
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if path.is_file()
+            && is_skeleton_target(path)
+            && !state.is_ignored(path)
+            && let Some(key) = canonical_key(&state.root, path)
+        {
+            out.push((key, path.to_path_buf()));
+        }
+    }
+    out
+}
+
+/// Walk the root and skeletonize every tracked .ts/.tsx file. Returns the
+/// keys added.
+pub fn perform_initial_sweep(state: &Arc<AppState>) -> Vec<String> {
+    let mut added = Vec::new();
+    for (key, path) in walk_targets(state, &state.root) {
+        match skeletonize_file(&path) {
+            Ok(ir) => {
+                state.upsert(key.clone(), ir);
+                added.push(key);
+            }
+            Err(e) => {
+                tracing::warn!("initial sweep: skipping {}: {}", path.display(), e);
+            }
+        }
+    }
+    added
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -244,6 +486,40 @@ mod tests {
         );
     }
 
+    /// Resolves `@/b` only once invalidated, standing in for a resolver that
+    /// reads tsconfig `paths`.
+    struct AliasAfterInvalidate(std::sync::atomic::AtomicBool);
+
+    impl Resolver for AliasAfterInvalidate {
+        fn resolve(&self, _root: &Path, _importer: &str, spec: &str) -> Resolution {
+            if spec == "@/b" && self.0.load(std::sync::atomic::Ordering::SeqCst) {
+                Resolution::Internal("src/b.ts".into())
+            } else {
+                Resolution::Unresolved
+            }
+        }
+
+        fn invalidate(&self) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn reresolve_all_picks_up_resolver_changes() {
+        let mut state = AppState::new(PathBuf::from("/repo"));
+        state.resolver = Box::new(AliasAfterInvalidate(AtomicBool::new(false)));
+        let skel = |src: &str, name: &str| {
+            crate::skeleton::skeletonize_source(src, Path::new(name)).unwrap()
+        };
+        state.upsert("src/a.ts".into(), skel("import { b } from '@/b';\n", "a.ts"));
+        state.upsert("src/b.ts".into(), skel("export const b = 1;\n", "b.ts"));
+        assert!(state.dependents_of("src/b.ts").is_empty());
+
+        assert_eq!(state.reresolve_all(), vec!["src/a.ts".to_string()]);
+        assert_eq!(state.dependents_of("src/b.ts"), vec!["src/a.ts".to_string()]);
+        assert!(state.reresolve_all().is_empty(), "second pass changes nothing");
+    }
+
     #[test]
     fn canonical_key_rejects_paths_outside_root() {
         let root = Path::new("/repo");
@@ -252,35 +528,66 @@ mod tests {
         assert_eq!(canonical_key(root, Path::new("src/../../x.ts")), None);
         assert_eq!(canonical_key(root, Path::new(".")), None);
     }
-}
 
-/// Walk the root and skeletonize every tracked .ts/.tsx file. Returns the
-/// keys added. `.git/` and `node_modules/` are pruned during descent — on
-/// large or slow filesystems, even visiting their entries is expensive.
-pub fn perform_initial_sweep(state: &Arc<AppState>) -> Vec<String> {
-    let mut added = Vec::new();
-    let walker = WalkBuilder::new(&state.root)
-        .filter_entry(|e| {
-            let name = e.file_name().to_str().unwrap_or("");
-            name != ".git" && name != "node_modules"
-        })
-        .build();
-    for entry in walker.flatten() {
-        let path = entry.path();
-        if path.is_file() && is_skeleton_target(path) && !state.is_ignored(path) {
-            let Some(key) = canonical_key(&state.root, path) else {
-                continue;
-            };
-            match skeletonize_file(path) {
-                Ok(ir) => {
-                    state.upsert(key.clone(), ir);
-                    added.push(key);
-                }
-                Err(e) => {
-                    tracing::warn!("initial sweep: skipping {}: {}", path.display(), e);
-                }
+    struct UtilsOnly;
+
+    impl Resolver for UtilsOnly {
+        fn resolve(&self, _root: &Path, _importer: &str, spec: &str) -> Resolution {
+            match spec {
+                "./utils" => Resolution::Internal("src/utils.ts".into()),
+                _ => Resolution::Unresolved,
             }
         }
     }
-    added
+
+    #[test]
+    fn symbol_index_tracks_consumed_exports() {
+        let mut state = AppState::new(PathBuf::from("/repo"));
+        state.resolver = Box::new(UtilsOnly);
+        let skel = |src: &str| {
+            crate::skeleton::skeletonize_source(src, Path::new("a.ts")).unwrap()
+        };
+        state.upsert("src/utils.ts".into(), skel("export const a = 1, b = 2;\n"));
+        state.upsert(
+            "src/x.ts".into(),
+            skel("import { a } from './utils';\nimport d, { b as c } from './utils';\n"),
+        );
+        let node = state.skeleton_graph.get("src/x.ts").unwrap().clone();
+        assert_eq!(
+            node.symbol_dependencies.get("src/utils.ts").unwrap(),
+            &vec!["a".to_string(), "b".to_string(), "default".to_string()]
+        );
+        assert_eq!(state.symbol_dependents_of("src/utils.ts", "b"), vec!["src/x.ts".to_string()]);
+
+        state.upsert("src/x.ts".into(), skel("import { a } from './utils';\n"));
+        assert!(state.symbol_dependents_of("src/utils.ts", "b").is_empty());
+        assert_eq!(state.symbol_dependents_of("src/utils.ts", "a"), vec!["src/x.ts".to_string()]);
+
+        state.remove("src/x.ts");
+        assert!(state.symbol_dependents.is_empty());
+    }
+
+    #[test]
+    fn import_cycles_are_strongly_connected_components() {
+        let files = [
+            ("src/a.ts", "import './b';\n"),
+            ("src/b.ts", "import './c';\n"),
+            ("src/c.ts", "import './a';\nimport './d';\n"),
+            ("src/d.ts", "import './e';\n"),
+            ("src/e.ts", "import './d';\n"),
+            ("src/f.ts", "import './a';\n"),
+        ];
+        let mut state = AppState::new(PathBuf::from("/repo"));
+        state.resolver = Box::new(crate::resolve::KeySetResolver {
+            keys: files.iter().map(|(k, _)| k.to_string()).collect(),
+        });
+        for (key, src) in files {
+            let skel = crate::skeleton::skeletonize_source(src, Path::new(key)).unwrap();
+            state.upsert(key.into(), skel);
+        }
+        assert_eq!(
+            import_cycles(&state),
+            vec![vec!["src/a.ts", "src/b.ts", "src/c.ts"], vec!["src/d.ts", "src/e.ts"]]
+        );
+    }
 }
@@ -1,8 +1,23 @@
+mod api;
+mod architecture;
+mod calls;
+mod check;
+mod churn;
+mod cli;
+mod components;
+mod coverage;
 mod dashboard;
+mod git;
 mod graph;
+mod metrics;
 mod protocol;
 mod resolve;
+mod review;
+mod revision;
+mod routes;
 mod skeleton;
+mod symbols;
+mod types;
 mod watcher;
 
 use anyhow::{Context, Result};
@@ -52,8 +67,18 @@ async fn write_notification(
 async fn main() -> Result<()> {
     tracing_subscriber::fmt().with_writer(std::io::stderr).init();
 
+    let command = match cli::parse(&std::env::args().collect::<Vec<_>>()) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
     let root = parse_root_arg()?;
     let state = Arc::new(AppState::new(root));
+    if command != cli::Command::Serve {
+        std::process::exit(cli::run(state, command).await);
+    }
 
     let (notify_tx, mut notify_rx) = mpsc::channel::<ChangeSet>(100);
 
@@ -81,6 +106,7 @@ async fn main() -> Result<()> {
             .send(ChangeSet {
                 added,
                 force_list_changed: true,
+                rules_changed: true,
                 ..ChangeSet::default()
             })
             .await;
@@ -119,6 +145,17 @@ async fn main() -> Result<()> {
     loop {
         tokio::select! {
             Some(changes) = notify_rx.recv() => {
+                // Off the select loop and under the batch gate, like the other
+                // whole-graph readers: a rules reload re-checks every file.
+                let changes = tokio::task::spawn_blocking({
+                    let state = state.clone();
+                    move || {
+                        let _gate = state.batch_gate.read().unwrap();
+                        architecture::refresh(&state, &changes);
+                        changes
+                    }
+                })
+                .await?;
                 let subs = state.subscriptions.read().unwrap().clone();
                 // Per-URI `updated` pushes go only to subscribed URIs;
                 // `list_changed` is always allowed.
@@ -134,14 +171,16 @@ async fn main() -> Result<()> {
                         .await?;
                     }
                 }
-                if subs.contains(protocol::GLOBAL_URI) {
-                    write_notification(
-                        &mut stdout,
-                        &state,
-                        "notifications/resources/updated",
-                        json!({"uri": protocol::GLOBAL_URI}),
-                    )
-                    .await?;
+                for uri in [protocol::GLOBAL_URI, protocol::ROUTES_URI] {
+                    if subs.contains(uri) {
+                        write_notification(
+                            &mut stdout,
+                            &state,
+                            "notifications/resources/updated",
+                            json!({"uri": uri}),
+                        )
+                        .await?;
+                    }
                 }
                 if changes.force_list_changed
                     || !changes.added.is_empty()
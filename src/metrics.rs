@@ -0,0 +1,253 @@
+//! Per-file and per-directory coupling metrics over the import graph.
+//!
+//! Fan-in counts importers (the `dependents` index), fan-out imported files;
+//! instability is Robert Martin's `I = Ce / (Ca + Ce)`. Depth is the
+//! shortest import distance from an entrypoint: files matching the given
+//! globs, or by default every file nothing imports.
+
+use ignore::gitignore::Gitignore;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::graph::{key_matches, AppState};
+
+pub const SORT_KEYS: &[&str] = &[
+    "fan_in",
+    "fan_out",
+    "instability",
+    "transitive_dependencies",
+    "depth",
+    "symbols",
+    "external_packages",
+    "name",
+];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Metrics {
+    /// File key, or directory (`.` for the root) when grouped.
+    pub name: String,
+    /// Files in the directory; absent per file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<usize>,
+    pub fan_in: usize,
+    pub fan_out: usize,
+    /// `fan_out / (fan_in + fan_out)`; absent for isolated files.
+    pub instability: Option<f64>,
+    pub transitive_dependencies: usize,
+    /// Imports away from the nearest entrypoint; absent if unreachable.
+    pub depth: Option<usize>,
+    /// Symbol counts by kind.
+    pub symbols: BTreeMap<String, usize>,
+    pub external_packages: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Report {
+    pub entrypoints: usize,
+    /// Rows before `limit` was applied.
+    pub total: usize,
+    pub rows: Vec<Metrics>,
+}
+
+/// Directory of a key, `.` at the root.
+fn directory(key: &str) -> &str {
+    key.rsplit_once('/').map(|(dir, _)| dir).unwrap_or(".")
+}
+
+fn instability(fan_in: usize, fan_out: usize) -> Option<f64> {
+    let total = fan_in + fan_out;
+    (total > 0).then(|| (fan_out as f64 / total as f64 * 100.0).round() / 100.0)
+}
+
+struct Graph {
+    keys: Vec<String>,
+    deps: HashMap<String, Vec<String>>,
+    importers: HashMap<String, Vec<String>>,
+}
+
+impl Graph {
+    fn new(state: &AppState) -> Graph {
+        let mut keys: Vec<String> = state.skeleton_graph.iter().map(|e| e.key().clone()).collect();
+        keys.sort();
+        let deps = keys
+            .iter()
+            .map(|k| {
+                let node = state.skeleton_graph.get(k);
+                (k.clone(), node.map(|n| n.dependencies.clone()).unwrap_or_default())
+            })
+            .collect();
+        let importers = keys.iter().map(|k| (k.clone(), state.dependents_of(k))).collect();
+        Graph { keys, deps, importers }
+    }
+
+    /// Files reachable from `starts` through imports, `starts` excluded
+    /// unless reached again through a cycle.
+    fn reachable<'a>(&'a self, starts: impl IntoIterator<Item = &'a str>) -> HashSet<&'a str> {
+        let mut seen = HashSet::new();
+        let mut queue: VecDeque<&str> = starts.into_iter().collect();
+        while let Some(key) = queue.pop_front() {
+            for dep in self.deps.get(key).into_iter().flatten() {
+                if seen.insert(dep.as_str()) {
+                    queue.push_back(dep);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Shortest distance of every reachable file from the entrypoints.
+    fn depths<'a>(&'a self, entrypoints: &[&'a str]) -> HashMap<&'a str, usize> {
+        let mut depth: HashMap<&str, usize> = entrypoints.iter().map(|k| (*k, 0)).collect();
+        let mut queue: VecDeque<&str> = entrypoints.iter().copied().collect();
+        while let Some(key) = queue.pop_front() {
+            let next = depth[key] + 1;
+            for dep in self.deps.get(key).into_iter().flatten() {
+                if !depth.contains_key(dep.as_str()) {
+                    depth.insert(dep, next);
+                    queue.push_back(dep);
+                }
+            }
+        }
+        depth
+    }
+}
+
+/// Metrics for every file, or every directory when `by_directory`, sorted
+/// by `sort_by` (largest first; `name` and `depth` ascending) and cut to
+/// `limit`. `only` narrows the rows to one file or directory.
+pub fn compute(
+    state: &AppState,
+    entrypoints: Option<&Gitignore>,
+    by_directory: bool,
+    only: Option<&str>,
+    sort_by: &str,
+    limit: usize,
+) -> Report {
+    let graph = Graph::new(state);
+    let entry_keys: Vec<&str> = graph
+        .keys
+        .iter()
+        .filter(|k| match entrypoints {
+            Some(globs) => key_matches(globs, k),
+            None => graph.importers[*k].is_empty(),
+        })
+        .map(String::as_str)
+        .collect();
+    let depths = graph.depths(&entry_keys);
+
+    let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for key in &graph.keys {
+        let group = if by_directory { directory(key) } else { key.as_str() };
+        groups.entry(group).or_default().push(key);
+    }
+
+    let mut rows: Vec<Metrics> = groups
+        .into_iter()
+        .filter(|(name, _)| only.is_none_or(|o| o == *name))
+        .map(|(name, members)| {
+            let inside: HashSet<&str> = members.iter().copied().collect();
+            let outside = |keys: &mut dyn Iterator<Item = &String>| {
+                keys.map(String::as_str)
+                    .filter(|k| !inside.contains(k))
+                    .collect::<BTreeSet<&str>>()
+                    .len()
+            };
+            let fan_in = outside(&mut members.iter().flat_map(|k| &graph.importers[*k]));
+            let fan_out = outside(&mut members.iter().flat_map(|k| &graph.deps[*k]));
+            let transitive = graph
+                .reachable(members.iter().copied())
+                .into_iter()
+                .filter(|k| !inside.contains(k))
+                .count();
+            let mut symbols: BTreeMap<String, usize> = BTreeMap::new();
+            let mut packages: BTreeSet<String> = BTreeSet::new();
+            for key in &members {
+                if let Some(node) = state.skeleton_graph.get(*key) {
+                    for symbol in &node.symbols {
+                        *symbols.entry(symbol.kind.clone()).or_default() += 1;
+                    }
+                    packages.extend(node.external_deps.iter().cloned());
+                }
+            }
+            Metrics {
+                name: name.to_string(),
+                files: by_directory.then_some(members.len()),
+                fan_in,
+                fan_out,
+                instability: instability(fan_in, fan_out),
+                transitive_dependencies: transitive,
+                depth: members.iter().filter_map(|k| depths.get(k).copied()).min(),
+                symbols,
+                external_packages: packages.len(),
+            }
+        })
+        .collect();
+
+    let symbol_total = |m: &Metrics| m.symbols.values().sum::<usize>();
+    rows.sort_by(|a, b| {
+        let ordering = match sort_by {
+            "fan_out" => b.fan_out.cmp(&a.fan_out),
+            "instability" => b.instability.unwrap_or(-1.0).total_cmp(&a.instability.unwrap_or(-1.0)),
+            "transitive_dependencies" => b.transitive_dependencies.cmp(&a.transitive_dependencies),
+            // Unreachable files sort last.
+            "depth" => a.depth.unwrap_or(usize::MAX).cmp(&b.depth.unwrap_or(usize::MAX)),
+            "symbols" => symbol_total(b).cmp(&symbol_total(a)),
+            "external_packages" => b.external_packages.cmp(&a.external_packages),
+            "name" => std::cmp::Ordering::Equal,
+            _ => b.fan_in.cmp(&a.fan_in),
+        };
+        ordering.then_with(|| a.name.cmp(&b.name))
+    });
+    let total = rows.len();
+    rows.truncate(limit);
+    Report {
+        entrypoints: entry_keys.len(),
+        total,
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::glob_matcher;
+    use crate::symbols::tests::project;
+
+    #[test]
+    fn computes_coupling_depth_and_directory_rollups() {
+        let (_dir, state) = project(&[
+            ("src/main.ts", "import { a } from './lib/a';\nimport React from 'react';\n"),
+            ("src/lib/a.ts", "import { b } from './b';\nexport function a() {}\nexport class C { m() {} }\n"),
+            ("src/lib/b.ts", "import { c } from '../util/c';\nimport z from 'zod';\nexport const b = 1;\n"),
+            ("src/util/c.ts", "export type T = string;\nexport const c = 1;\n"),
+            ("src/orphan.ts", "export const o = 1;\n"),
+        ]);
+        let report = compute(&state, None, false, None, "fan_in", 100);
+        assert_eq!(report.entrypoints, 2, "main.ts and orphan.ts have no importers");
+        let row = |name: &str| report.rows.iter().find(|r| r.name == name).unwrap().clone();
+        let a = row("src/lib/a.ts");
+        assert_eq!((a.fan_in, a.fan_out, a.instability), (1, 1, Some(0.5)));
+        assert_eq!((a.transitive_dependencies, a.depth), (2, Some(1)));
+        assert_eq!(a.symbols, BTreeMap::from([("class".into(), 1), ("function".into(), 1), ("method".into(), 1)]));
+        let c = row("src/util/c.ts");
+        assert_eq!((c.instability, c.depth), (Some(0.0), Some(3)));
+        assert_eq!(row("src/orphan.ts").instability, None);
+        assert_eq!(row("src/main.ts").external_packages, 1);
+
+        let report = compute(&state, None, true, None, "name", 100);
+        let dirs: Vec<(&str, Option<usize>, usize, usize)> = report
+            .rows
+            .iter()
+            .map(|r| (r.name.as_str(), r.files, r.fan_in, r.fan_out))
+            .collect();
+        assert_eq!(dirs, [("src", Some(2), 0, 1), ("src/lib", Some(2), 1, 1), ("src/util", Some(1), 1, 0)]);
+
+        let globs = glob_matcher(&state.root, &["src/lib/b.ts".to_string()]).unwrap();
+        let report = compute(&state, Some(&globs), false, None, "depth", 2);
+        assert_eq!((report.total, report.rows.len()), (5, 2));
+        assert_eq!(report.rows[0].name, "src/lib/b.ts");
+        assert_eq!(report.rows[1].depth, Some(1));
+        let report = compute(&state, None, false, Some("src/lib/b.ts"), "fan_in", 10);
+        assert_eq!(report.rows.len(), 1);
+    }
+}
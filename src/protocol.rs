@@ -4,12 +4,26 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use crate::graph::AppState;
+use crate::api::{self, ApiSurface};
+use crate::architecture;
+use crate::churn;
+use crate::coverage;
+use crate::git;
+use crate::graph::{self, AppState};
+use crate::metrics;
+use crate::review;
+use crate::revision;
+use crate::routes;
 use crate::skeleton;
+use crate::symbols;
 
 pub const GLOBAL_URI: &str = "skeleton://project/global";
+pub const ROUTES_URI: &str = "skeleton://project/routes";
 const FILE_URI_PREFIX: &str = "skeleton://project/file/";
 
+/// Upper bound on `depth` for get_callers / get_callees.
+const MAX_CALL_DEPTH: u64 = 10;
+
 /// Protocol versions this server implements, newest first.
 const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
 
@@ -29,9 +43,28 @@ pub fn file_uri(key: &str) -> String {
     format!("{}{}", FILE_URI_PREFIX, utf8_percent_encode(key, URI_ENCODE))
 }
 
-fn decode_file_uri(uri: &str) -> Option<String> {
-    uri.strip_prefix(FILE_URI_PREFIX)
-        .map(|p| percent_decode_str(p).decode_utf8_lossy().into_owned())
+/// Path and optional `?rev=` of a file URI. Keys always escape `?`, so a
+/// raw one starts the query.
+fn decode_file_uri(uri: &str) -> Option<(String, Option<String>)> {
+    let rest = uri.strip_prefix(FILE_URI_PREFIX)?;
+    let (path, query) = match rest.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (rest, None),
+    };
+    let decode = |s: &str| percent_decode_str(s).decode_utf8_lossy().into_owned();
+    let rev = query.and_then(|q| {
+        q.split('&')
+            .find_map(|pair| pair.strip_prefix("rev="))
+            .map(decode)
+    });
+    Some((decode(path), rev))
+}
+
+/// Skeleton of `key` as of git revision `rev`, parsed outside the live
+/// graph. Imports are left unresolved.
+fn skeleton_at_rev(state: &AppState, key: &str, rev: &str) -> anyhow::Result<skeleton::FileSkeleton> {
+    let source_text = git::show_file(&state.root, rev, key)?;
+    skeleton::skeletonize_source(&source_text, std::path::Path::new(key))
 }
 
 // --- MCP PROTOCOL STRUCTURES ---
@@ -127,6 +160,10 @@ fn handle_resources_list(state: &AppState) -> Value {
         "uri": GLOBAL_URI,
         "name": "Global Semantic Skeleton",
         "mimeType": "application/json"
+    }), json!({
+        "uri": ROUTES_URI,
+        "name": "Framework Routes",
+        "mimeType": "application/json"
     })];
     for entry in state.skeleton_graph.iter() {
         let path = entry.key();
@@ -139,7 +176,7 @@ fn handle_resources_list(state: &AppState) -> Value {
     json!({ "resources": resources })
 }
 
-fn handle_resources_read(state: &AppState, params: Option<&Value>) -> Result<Value, Value> {
+pub async fn handle_resources_read(state: &Arc<AppState>, params: Option<&Value>) -> Result<Value, Value> {
     let uri = params
         .and_then(|p| p.get("uri"))
         .and_then(|u| u.as_str())
@@ -147,8 +184,11 @@ fn handle_resources_read(state: &AppState, params: Option<&Value>) -> Result<Val
 
     if uri == GLOBAL_URI {
         let mut graph = HashMap::new();
-        for entry in state.skeleton_graph.iter() {
-            graph.insert(entry.key().clone(), entry.value().clone());
+        {
+            let _gate = state.batch_gate.read().unwrap();
+            for entry in state.skeleton_graph.iter() {
+                graph.insert(entry.key().clone(), entry.value().clone());
+            }
         }
         let mut contents = vec![json!({
             "uri": GLOBAL_URI,
@@ -165,8 +205,43 @@ fn handle_resources_read(state: &AppState, params: Option<&Value>) -> Result<Val
         return Ok(json!({ "contents": contents }));
     }
 
-    if let Some(path) = decode_file_uri(uri) {
+    if uri == ROUTES_URI {
+        let found = {
+            let _gate = state.batch_gate.read().unwrap();
+            routes::all_routes(state, None)
+        };
+        return Ok(json!({
+            "contents": [{
+                "uri": ROUTES_URI,
+                "mimeType": "application/json",
+                "text": serde_json::to_string(&found).unwrap_or_default()
+            }]
+        }));
+    }
+
+    if let Some((path, rev)) = decode_file_uri(uri) {
         let key = state.key_for(&path).unwrap_or(path);
+        if let Some(rev) = rev {
+            let at_rev = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || skeleton_at_rev(&state, &key, &rev)
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            return match at_rev {
+                Ok(file_skeleton) => Ok(json!({
+                    "contents": [{
+                        "uri": uri,
+                        "mimeType": "application/json",
+                        "text": serde_json::to_string(&file_skeleton).unwrap_or_default()
+                    }]
+                })),
+                Err(e) => Err(json!({
+                    "code": -32002,
+                    "message": format!("Resource not found: {} ({})", uri, e)
+                })),
+            };
+        }
         return match state.skeleton_graph.get(&key) {
             Some(file_skeleton) => Ok(json!({
                 "contents": [{
@@ -202,28 +277,42 @@ fn handle_subscription(state: &AppState, params: Option<&Value>, subscribe: bool
     Ok(json!({}))
 }
 
+/// An API surface snapshot given inline or as a path to a saved JSON file
+/// (relative to the project root).
+fn load_snapshot(state: &AppState, value: &Value) -> anyhow::Result<ApiSurface> {
+    match value {
+        Value::String(path) => {
+            let text = std::fs::read_to_string(state.root.join(path))?;
+            Ok(serde_json::from_str(&text)?)
+        }
+        other => Ok(serde_json::from_value(other.clone())?),
+    }
+}
+
 fn tools_list() -> Value {
     json!({
         "tools": [
             {
                 "name": "get_implementation",
-                "description": "Returns the original source text of a single named node: a top-level function, class, class method (ClassName.methodName), arrow/function-expression binding, or the default export (target_node: \"default\").",
+                "description": "Returns the original source text of a single named node. target_node is a dotted path through classes (methods, getters/setters, properties, `static` blocks), namespaces, object literals, interfaces/type literals and enums — e.g. \"validateUser\", \"UserService.getUser\", \"Utils.format\", \"api.fetchUser\", \"Color.Red\" — or \"default\" for the default export. Overloads and get/set pairs are returned together.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
                         "file_path": { "type": "string" },
-                        "target_node": { "type": "string" }
+                        "target_node": { "type": "string" },
+                        "rev": { "type": "string", "description": "Git revision (branch, tag, commit) to read the file at instead of the working tree; the live graph is not touched." }
                     },
                     "required": ["file_path", "target_node"]
                 }
             },
             {
                 "name": "list_symbols",
-                "description": "Lists every top-level symbol in a file as {name, kind, exported, signature}. Kinds: function, arrow_function, class, method, interface, type, enum, variable, component.",
+                "description": "Lists every top-level symbol in a file as {name, kind, exported, signature, location}; location holds the byte span (start, end) and 1-based start_line/start_column/end_line/end_column. Kinds: function, arrow_function, class, method, interface, type, enum, variable, component, reexport (a name forwarded from another module, e.g. in barrel files). Methods also carry method_kind (method, getter, setter, constructor); symbols may carry modifiers (static, abstract, private, protected, public, override, async, declare), overloads [{signature, implementation}] for TypeScript overload sets, and parameter_properties for constructors.",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "file_path": { "type": "string" }
+                        "file_path": { "type": "string" },
+                        "rev": { "type": "string", "description": "Git revision (branch, tag, commit) to read the file at instead of the working tree; the live graph is not touched." }
                     },
                     "required": ["file_path"]
                 }
@@ -234,26 +323,28 @@ fn tools_list() -> Value {
                 "inputSchema": {
                     "type": "object",
                     "properties": {
-                        "file_path": { "type": "string" }
+                        "file_path": { "type": "string" },
+                        "rev": { "type": "string", "description": "Git revision (branch, tag, commit) to read the file at instead of the working tree; the live graph is not touched." }
                     },
                     "required": ["file_path"]
                 }
             },
             {
                 "name": "get_dependencies",
-                "description": "Resolved import edges for a file: which files it imports, which files import it, and its external packages.",
+                "description": "Resolved import edges for a file: which files it imports (with the export names consumed from each, in imported_symbols), which files import it, and its external packages. With `symbol`, imported_by is narrowed to files consuming that export — directly, via namespace imports, or through re-exporting barrels — as [{file, via}].",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
                         "file_path": { "type": "string" },
-                        "direction": { "type": "string", "enum": ["in", "out", "both"], "description": "Edge direction to include (default both)." }
+                        "direction": { "type": "string", "enum": ["in", "out", "both"], "description": "Edge direction to include (default both)." },
+                        "symbol": { "type": "string", "description": "Export name to narrow imported_by to." }
                     },
                     "required": ["file_path"]
                 }
             },
             {
                 "name": "search_symbols",
-                "description": "Case-insensitive substring search for symbol names across the whole graph. Returns [{file, name, kind}].",
+                "description": "Case-insensitive substring search for symbol names across the whole graph. Returns [{file, name, kind, location}].",
                 "inputSchema": {
                     "type": "object",
                     "properties": {
@@ -261,12 +352,203 @@ fn tools_list() -> Value {
                     },
                     "required": ["query"]
                 }
+            },
+            {
+                "name": "resolve_export",
+                "description": "Follows a name exported by a file through barrel re-exports (export { X } from, export *, imports exported onwards) to the file that declares it. Returns {status, ...}: defined (file, symbol, chain), namespace (file, chain), external (package, name, chain), ambiguous (candidates: several export * sources provide the name), or not_found (reason). chain lists each hop as {file, name, via}.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string" },
+                        "name": { "type": "string", "description": "Exported name, or \"default\"." }
+                    },
+                    "required": ["file_path", "name"]
+                }
+            },
+            {
+                "name": "find_references",
+                "description": "Lists every file importing the symbol a file exports under `name`, including imports through barrel re-exports and renaming aliases. Returns {definition, references: [{file, local, imported, kind, type_only, location, usages?}]}; kind is named, default, namespace, dynamic (a string-literal import()) or reexport. With include_usages, each importing file is scanned for identifier references to the local binding (or ns.member for namespace imports), reported as locations.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string" },
+                        "name": { "type": "string", "description": "Exported name, or \"default\"." },
+                        "include_usages": { "type": "boolean", "description": "Also report usage locations (default false)." }
+                    },
+                    "required": ["file_path", "name"]
+                }
+            },
+            {
+                "name": "get_callers",
+                "description": "Functions, arrow bindings and methods that call target_node (a top-level name or Class.method): in the same file, and — for exported functions — in files importing it, through barrels and namespace imports. Returns [{file, node, lines, depth, cross_file}], transitively up to depth. Syntactic call graph (calls by identifier, this.method(), ns.fn()); no type inference, so callbacks and dynamic dispatch are missed.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string" },
+                        "target_node": { "type": "string" },
+                        "depth": { "type": "integer", "description": "Levels of transitive callers (default 1, max 10)." }
+                    },
+                    "required": ["file_path", "target_node"]
+                }
+            },
+            {
+                "name": "get_callees",
+                "description": "Functions, arrow bindings and methods that target_node calls: in the same file, and imported functions resolved to their declaring file. Returns [{file, node, lines, depth, cross_file}], transitively up to depth; lines are in the calling file. Syntactic call graph (calls by identifier, this.method(), ns.fn()); no type inference.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string" },
+                        "target_node": { "type": "string" },
+                        "depth": { "type": "integer", "description": "Levels of transitive callees (default 1, max 10)." }
+                    },
+                    "required": ["file_path", "target_node"]
+                }
+            },
+            {
+                "name": "find_type_usages",
+                "description": "Lists symbols whose signatures reference a type — parameter and return types, extends/implements, property types — resolved through imports, aliases and barrels to the type's declaration. Returns {definition, usages: [{file, symbol, kind, exported, written_as, location}]}. By default only exported, non-private symbols are reported (the type's footprint on public signatures); include_internal adds the rest.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string" },
+                        "name": { "type": "string", "description": "Type name as exported (or declared) by file_path." },
+                        "include_internal": { "type": "boolean", "description": "Also report non-exported and private symbols (default false)." }
+                    },
+                    "required": ["file_path", "name"]
+                }
+            },
+            {
+                "name": "find_dead_exports",
+                "description": "Reports exported declarations that no other file in the graph consumes. Imports through export * barrels, namespace imports and string-literal import() all count as consumption. Files matching the entrypoints globs (gitignore syntax, e.g. \"src/pages/**\", \"*.stories.tsx\") are treated as consumed from outside. Returns [{file, name, kind, location, confidence, notes}]; confidence is high (the file is imported, just not this export), medium (only forwarded by barrels nobody imports it from) or low (no file imports the file at all).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "entrypoints": { "type": "array", "items": { "type": "string" }, "description": "Globs of files consumed outside the graph." }
+                    }
+                }
+            },
+            {
+                "name": "get_api_surface",
+                "description": "Flattened public API of a package entrypoint: every name it exports, following export * and re-exports across the graph to the declaring file. Returns {entry, exports: [{name, kind, signature, file, members: [{name, signature}]}], unresolved_stars}. Classes, interfaces, enums and object types list their public members separately; overloads are one signature per line. Save the JSON as a snapshot for diff_api_surface.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "Package entry file, e.g. src/index.ts." }
+                    },
+                    "required": ["file_path"]
+                }
+            },
+            {
+                "name": "diff_api_surface",
+                "description": "Compares two get_api_surface snapshots and classifies each change as added, removed, kind_changed or signature_changed, flagging likely semver-breaking ones: removals, changed or required-added parameters and members, return type changes. Returns {semver: major|minor|patch, changes: [{name, change, breaking, additive, before, after, details}]}. Each snapshot is inline JSON or the path of a saved JSON file; after defaults to the current surface of file_path (or of the before snapshot's entry).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "before": { "description": "Snapshot object, or path to a saved snapshot JSON file." },
+                        "after": { "description": "Snapshot object, or path to a saved snapshot JSON file (default: current surface)." },
+                        "file_path": { "type": "string", "description": "Entry to compute the current surface for when after is omitted." }
+                    },
+                    "required": ["before"]
+                }
+            },
+            {
+                "name": "diff_revisions",
+                "description": "Structural diff of the graph between two git revisions, or a revision and the working tree: per file, added/removed/changed symbols by signature (body-only edits are ignored), added/removed import edges and external packages, plus import cycles introduced or broken. Returns {from, to, files: [{file, status, added_symbols, removed_symbols, changed_symbols: [{before, after}], added_imports, removed_imports, added_packages, removed_packages}], new_cycles, removed_cycles}. Revisions are read from the local repository; the live graph is not touched.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "from": { "type": "string", "description": "Base git revision (branch, tag, commit)." },
+                        "to": { "type": "string", "description": "Head git revision (default: the working tree)." }
+                    },
+                    "required": ["from"]
+                }
+            },
+            {
+                "name": "get_review_context",
+                "description": "Everything a reviewer needs for the current branch in one call: files changed since the merge base with base (committed, staged, unstaged and untracked), each with its current skeleton and the changed_symbols its diff hunks overlap, plus first-level dependents — unchanged files importing them. Returns {base, merge_base, files: [{file, status, changed_symbols, skeleton}], dependents: [{file, imports}], other_changes}; status is added, modified, deleted or untracked, and other_changes lists changed paths outside the graph.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "base": { "type": "string", "description": "Branch the work is compared against (default: origin/HEAD, then main, then master)." }
+                    }
+                }
+            },
+            {
+                "name": "get_change_hotspots",
+                "description": "Local git history joined with the import graph. Walks recent non-merge commits and returns {commits, churn, hotspots, hidden_coupling}: churn lists the most-changed files as {file, commits, last_changed, fan_in, fan_out, score}; hotspots ranks them by score = commits × fan_in (change that ripples to many importers); hidden_coupling lists file pairs that change together without an import edge either way, as {a, b, together, degree} with degree = together / average churn of the pair. Only files currently in the graph are counted.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "max_commits": { "type": "integer", "description": "Newest commits to walk (default 500)." },
+                        "since": { "type": "string", "description": "Only commits after this date, in git log --since syntax (\"2024-01-01\", \"6 months ago\")." },
+                        "min_co_changes": { "type": "integer", "description": "Minimum shared commits for a hidden_coupling pair (default 3)." },
+                        "max_commit_files": { "type": "integer", "description": "Commits touching more graph files than this are left out of co-change (default 30)." },
+                        "limit": { "type": "integer", "description": "Rows per list (default 20)." }
+                    }
+                }
+            },
+            {
+                "name": "graph_metrics",
+                "description": "Coupling metrics per file or per directory: fan_in (importing files), fan_out (imported files), instability = fan_out / (fan_in + fan_out) (0 = stable, 1 = unstable; absent for isolated files), transitive_dependencies, depth (shortest import distance from an entrypoint; absent if unreachable), symbols (counts by kind) and external_packages. For directories, edges inside the directory are not counted and files gives its size. Returns {entrypoints, total, rows}, sorted and cut to limit.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "group_by": { "type": "string", "enum": ["file", "directory"], "description": "Row granularity (default file)." },
+                        "sort_by": { "type": "string", "enum": ["fan_in", "fan_out", "instability", "transitive_dependencies", "depth", "symbols", "external_packages", "name"], "description": "Sort key, largest first except depth and name (default fan_in)." },
+                        "limit": { "type": "integer", "description": "Maximum rows (default 50)." },
+                        "file_path": { "type": "string", "description": "Report only this file (or directory, with group_by directory)." },
+                        "entrypoints": { "type": "array", "items": { "type": "string" }, "description": "Gitignore-style globs for the files depth is measured from (default: files nothing imports)." }
+                    }
+                }
+            },
+            {
+                "name": "check_architecture",
+                "description": "Layer-rule violations from the architecture rules in .skeletonizer.json, kept current as files change. Rules forbid importing files matching disallow globs from files matching from, importing a module's files from outside it except through its public entries, or importing disallow_packages. Returns {rules, error?, violations: [{rule, message, file, source, target, type_only, location}]}; each violation points at the offending import statement, and error reports an invalid config.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "Only violations in this importing file." },
+                        "rule": { "type": "string", "description": "Only violations of this rule name." }
+                    }
+                }
+            },
+            {
+                "name": "get_test_reachability",
+                "description": "Structural test mapping: which test files load a source file (through transitive imports) and which of its exports they consume, resolved through barrels and namespace imports. With file_path returns {file, is_test, tests: [{file, depth}], exports: [{name, kind, location, tests, direct}]} — tests answers \"which tests cover this file\", depth 1 meaning a direct import, and direct marks exports a test imports itself. Without file_path returns {test_files, files: [{file, tests, exports, untested_exports}]} for every non-test file, least-tested first. Measures what tests import, not what they assert.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "Report one file in detail (default: summary of all files)." },
+                        "test_globs": { "type": "array", "items": { "type": "string" }, "description": "Gitignore-style globs for test files (default: *.test.*, *.spec.*, __tests__/, /test/, /tests/; the last two only match at the project root)." }
+                    }
+                }
+            },
+            {
+                "name": "list_routes",
+                "description": "Lists pages and endpoints: Next.js app/ and pages/ files, Remix app/routes/ files, React Router <Route path> elements and route objects (createBrowserRouter, useRoutes, RouteObject[]), and Express/Fastify/Hono/Koa app.get(\"/path\", handler) calls. Returns [{file, path, framework, kind: page|endpoint, method, handler, location}] sorted by path; handler is the symbol serving the route (omitted for inline functions). Paths keep each framework's notation ([id], :id). Also available as the skeleton://project/routes resource.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "Limit to one file (default: all files)." },
+                        "framework": { "type": "string", "description": "Only routes of this framework: next-app, next-pages, remix, react-router, express, fastify, hono or koa." }
+                    }
+                }
+            },
+            {
+                "name": "list_components",
+                "description": "Lists React components (.tsx) with their props type and fields, destructuring default props, hooks called, memo/forwardRef wrappers and the child components they render. Props types imported from another file are resolved to their declaration (defined_in) and its fields. Returns [{file, name, wrappers, props: {type_name, defined_in, fields: [{name, type, optional}], extends}, default_props, hooks, renders, location}]. Without file_path, covers every file in the graph.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "file_path": { "type": "string", "description": "Limit to one file (default: all files)." }
+                    }
+                }
             }
         ]
     })
 }
 
-async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Result<Value, Value> {
+pub async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Result<Value, Value> {
     let params =
         params.ok_or_else(|| json!({"code": -32602, "message": "Missing params"}))?;
     let name = params.get("name").and_then(|n| n.as_str()).unwrap_or("");
@@ -287,14 +569,24 @@ async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Res
                 .and_then(|s| s.as_str())
                 .unwrap_or("")
                 .to_string();
-            let abs = state
-                .key_for(&file_path)
-                .map(|k| state.abs_path(&k))
+            let rev = args.get("rev").and_then(|r| r.as_str()).map(str::to_string);
+            let key = state.key_for(&file_path);
+            let abs = key
+                .as_ref()
+                .map(|k| state.abs_path(k))
                 .unwrap_or_else(|| std::path::PathBuf::from(&file_path));
             // Parse work runs off the reactor.
             let lookup = tokio::task::spawn_blocking({
                 let target = target.clone();
-                move || skeleton::get_implementation(&abs, &target)
+                let state = state.clone();
+                move || match rev {
+                    Some(rev) => {
+                        let key = key.ok_or_else(|| anyhow::anyhow!("file_path is outside the project root"))?;
+                        let source_text = git::show_file(&state.root, &rev, &key)?;
+                        skeleton::get_implementation_source(&source_text, &abs, &target)
+                    }
+                    None => skeleton::get_implementation(&abs, &target),
+                }
             })
             .await
             .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
@@ -307,6 +599,22 @@ async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Res
                     file_path,
                     candidates.join(", ")
                 )),
+                Ok(skeleton::ImplLookup::ChildNotFound { parent, children }) => {
+                    tool_error(if children.is_empty() {
+                        format!(
+                            "Node '{}' not found in {}: '{}' has no addressable members.",
+                            target, file_path, parent
+                        )
+                    } else {
+                        format!(
+                            "Node '{}' not found in {}. Available members of '{}': {}",
+                            target,
+                            file_path,
+                            parent,
+                            children.join(", ")
+                        )
+                    })
+                }
                 Err(e) => tool_error(format!("Failed to extract implementation: {}", e)),
             })
         }
@@ -314,26 +622,46 @@ async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Res
             let key = state
                 .key_for(&file_path)
                 .unwrap_or_else(|| file_path.clone());
-            Ok(match state.skeleton_graph.get(&key) {
-                Some(file_skeleton) => {
-                    let symbols: Vec<_> = file_skeleton
-                        .symbols
-                        .iter()
-                        .filter(|s| {
-                            name != "list_functions"
-                                || skeleton::CALLABLE_KINDS.contains(&s.kind.as_str())
-                        })
-                        .collect();
-                    tool_text(serde_json::to_string(&symbols).unwrap_or_default())
+            let file_skeleton = match args.get("rev").and_then(|r| r.as_str()) {
+                Some(rev) => {
+                    let at_rev = tokio::task::spawn_blocking({
+                        let state = state.clone();
+                        let rev = rev.to_string();
+                        move || skeleton_at_rev(&state, &key, &rev)
+                    })
+                    .await
+                    .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+                    match at_rev {
+                        Ok(file_skeleton) => file_skeleton,
+                        Err(e) => {
+                            return Ok(tool_error(format!(
+                                "Failed to read {} at {}: {}",
+                                file_path, rev, e
+                            )));
+                        }
+                    }
                 }
-                None => tool_error(format!("File not found in graph: {}", file_path)),
-            })
+                None => match state.skeleton_graph.get(&key) {
+                    Some(file_skeleton) => file_skeleton.clone(),
+                    None => return Ok(tool_error(format!("File not found in graph: {}", file_path))),
+                },
+            };
+            let symbols: Vec<_> = file_skeleton
+                .symbols
+                .iter()
+                .filter(|s| {
+                    name != "list_functions"
+                        || skeleton::CALLABLE_KINDS.contains(&s.kind.as_str())
+                })
+                .collect();
+            Ok(tool_text(serde_json::to_string(&symbols).unwrap_or_default()))
         }
         "get_dependencies" => {
             let direction = args
                 .get("direction")
                 .and_then(|s| s.as_str())
                 .unwrap_or("both");
+            let symbol = args.get("symbol").and_then(|s| s.as_str());
             let key = state
                 .key_for(&file_path)
                 .unwrap_or_else(|| file_path.clone());
@@ -342,10 +670,15 @@ async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Res
                     let mut out = serde_json::Map::new();
                     if direction == "out" || direction == "both" {
                         out.insert("imports".into(), json!(node.dependencies));
+                        out.insert("imported_symbols".into(), json!(node.symbol_dependencies));
                         out.insert("external".into(), json!(node.external_deps));
                     }
                     if direction == "in" || direction == "both" {
-                        out.insert("imported_by".into(), json!(state.dependents_of(&key)));
+                        let imported_by = match symbol {
+                            Some(symbol) => json!(symbols::symbol_importers(state, &key, symbol)),
+                            None => json!(state.dependents_of(&key)),
+                        };
+                        out.insert("imported_by".into(), imported_by);
                     }
                     tool_text(serde_json::to_string(&out).unwrap_or_default())
                 }
@@ -365,13 +698,397 @@ async fn handle_tools_call(state: &Arc<AppState>, params: Option<&Value>) -> Res
                         hits.push(json!({
                             "file": entry.key(),
                             "name": sym.name,
-                            "kind": sym.kind
+                            "kind": sym.kind,
+                            "location": sym.location
                         }));
                     }
                 }
             }
             Ok(tool_text(serde_json::to_string(&hits).unwrap_or_default()))
         }
+        "resolve_export" => {
+            let export_name = args.get("name").and_then(|s| s.as_str()).unwrap_or("");
+            let Some(key) = state.key_for(&file_path) else {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            };
+            if !state.skeleton_graph.contains_key(&key) {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            }
+            let resolution = symbols::resolve_export(state, &key, export_name);
+            Ok(tool_text(serde_json::to_string(&resolution).unwrap_or_default()))
+        }
+        "find_references" => {
+            let export_name = args
+                .get("name")
+                .and_then(|s| s.as_str())
+                .unwrap_or("")
+                .to_string();
+            let usages = args
+                .get("include_usages")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false);
+            let Some(key) = state.key_for(&file_path) else {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            };
+            if !state.skeleton_graph.contains_key(&key) {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            }
+            // Usage scans parse files; keep them off the reactor.
+            let found = tokio::task::spawn_blocking({
+                let state = state.clone();
+                let export_name = export_name.clone();
+                move || symbols::find_references(&state, &key, &export_name, usages)
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+
+            Ok(match found {
+                Ok(refs) => tool_text(serde_json::to_string(&refs).unwrap_or_default()),
+                Err(reason) => tool_error(reason),
+            })
+        }
+        "get_callers" | "get_callees" => {
+            let target = args
+                .get("target_node")
+                .and_then(|s| s.as_str())
+                .unwrap_or("");
+            let depth = args
+                .get("depth")
+                .and_then(|d| d.as_u64())
+                .unwrap_or(1)
+                .clamp(1, MAX_CALL_DEPTH) as usize;
+            let key = state
+                .key_for(&file_path)
+                .unwrap_or_else(|| file_path.clone());
+            let Some(node) = state.skeleton_graph.get(&key).map(|n| n.clone()) else {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            };
+            let known = node.symbols.iter().any(|s| s.name == target)
+                || node
+                    .call_graph
+                    .iter()
+                    .any(|e| e.caller == target || e.callee == target)
+                || node.imported_calls.iter().any(|c| c.caller == target);
+            if !known {
+                return Ok(tool_error(format!(
+                    "Node '{}' not found in {}. Available callables: {}",
+                    target,
+                    file_path,
+                    node.symbols
+                        .iter()
+                        .filter(|s| skeleton::CALLABLE_KINDS.contains(&s.kind.as_str()))
+                        .map(|s| s.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+            let sites = if name == "get_callers" {
+                symbols::callers(state, &key, target, depth)
+            } else {
+                symbols::callees(state, &key, target, depth)
+            };
+            Ok(tool_text(serde_json::to_string(&sites).unwrap_or_default()))
+        }
+        "find_type_usages" => {
+            let type_name = args.get("name").and_then(|s| s.as_str()).unwrap_or("");
+            let include_internal = args
+                .get("include_internal")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false);
+            let Some(key) = state.key_for(&file_path) else {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            };
+            if !state.skeleton_graph.contains_key(&key) {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            }
+            Ok(
+                match symbols::find_type_usages(state, &key, type_name, include_internal) {
+                    Ok(usages) => tool_text(serde_json::to_string(&usages).unwrap_or_default()),
+                    Err(reason) => tool_error(reason),
+                },
+            )
+        }
+        "get_api_surface" => {
+            let Some(key) = state.key_for(&file_path) else {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            };
+            if !state.skeleton_graph.contains_key(&key) {
+                return Ok(tool_error(format!("File not found in graph: {}", file_path)));
+            }
+            let surface = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || {
+                    let _gate = state.batch_gate.read().unwrap();
+                    serde_json::to_string(&api::surface(&state, &key))
+                }
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(tool_text(surface.unwrap_or_default()))
+        }
+        "diff_api_surface" => {
+            let before = match args.get("before").map(|v| load_snapshot(state, v)) {
+                Some(Ok(s)) => s,
+                Some(Err(e)) => return Ok(tool_error(format!("Invalid before snapshot: {}", e))),
+                None => return Ok(tool_error("Missing required argument: before".to_string())),
+            };
+            let after = match args.get("after").map(|v| load_snapshot(state, v)) {
+                Some(Ok(s)) => s,
+                Some(Err(e)) => return Ok(tool_error(format!("Invalid after snapshot: {}", e))),
+                None => {
+                    let entry = if file_path.is_empty() {
+                        before.entry.clone()
+                    } else {
+                        file_path.clone()
+                    };
+                    match state.key_for(&entry) {
+                        Some(key) if state.skeleton_graph.contains_key(&key) => {
+                            api::surface(state, &key)
+                        }
+                        _ => return Ok(tool_error(format!("File not found in graph: {}", entry))),
+                    }
+                }
+            };
+            Ok(tool_text(
+                serde_json::to_string(&api::diff(&before, &after)).unwrap_or_default(),
+            ))
+        }
+        "diff_revisions" => {
+            let Some(from) = args.get("from").and_then(|r| r.as_str()).map(str::to_string) else {
+                return Ok(tool_error("from is required".to_string()));
+            };
+            let to = args.get("to").and_then(|r| r.as_str()).map(str::to_string);
+            let report = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || -> Result<String, String> {
+                    let at = |rev: &str| {
+                        revision::graph_at(&state, rev)
+                            .map_err(|e| format!("Failed to read the graph at {}: {}", rev, e))
+                    };
+                    let before = at(&from)?;
+                    let diff = match &to {
+                        Some(to) => revision::diff(&before, &at(to)?, &from, to),
+                        None => {
+                            let _gate = state.batch_gate.read().unwrap();
+                            revision::diff(&before, &state, &from, "working tree")
+                        }
+                    };
+                    Ok(serde_json::to_string(&diff).unwrap_or_default())
+                }
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(match report {
+                Ok(text) => tool_text(text),
+                Err(e) => tool_error(e),
+            })
+        }
+        "get_review_context" => {
+            let base = args.get("base").and_then(|r| r.as_str()).map(str::to_string);
+            let report = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || -> anyhow::Result<String> {
+                    let base = match base {
+                        Some(base) => base,
+                        None => git::default_base(&state.root)?,
+                    };
+                    let _gate = state.batch_gate.read().unwrap();
+                    Ok(serde_json::to_string(&review::context(&state, &base)?)?)
+                }
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(match report {
+                Ok(text) => tool_text(text),
+                Err(e) => tool_error(format!("Failed to build review context: {}", e)),
+            })
+        }
+        "get_change_hotspots" => {
+            let opts: churn::Options = match serde_json::from_value(args.clone()) {
+                Ok(opts) => opts,
+                Err(e) => return Ok(tool_error(format!("Invalid arguments: {}", e))),
+            };
+            let report = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || churn::report(&state, &opts)
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(match report {
+                Ok(report) => tool_text(serde_json::to_string(&report).unwrap_or_default()),
+                Err(e) => tool_error(format!("Failed to read git history: {}", e)),
+            })
+        }
+        "graph_metrics" => {
+            let by_directory = match args.get("group_by").and_then(|g| g.as_str()) {
+                None | Some("file") => false,
+                Some("directory") => true,
+                Some(other) => return Ok(tool_error(format!("Unknown group_by: {}", other))),
+            };
+            let sort_by = args
+                .get("sort_by")
+                .and_then(|s| s.as_str())
+                .unwrap_or("fan_in")
+                .to_string();
+            if !metrics::SORT_KEYS.contains(&sort_by.as_str()) {
+                return Ok(tool_error(format!(
+                    "Unknown sort_by: {} (expected one of {})",
+                    sort_by,
+                    metrics::SORT_KEYS.join(", ")
+                )));
+            }
+            let limit = args.get("limit").and_then(|l| l.as_u64()).unwrap_or(50) as usize;
+            let entrypoints = match args.get("entrypoints").and_then(|g| g.as_array()) {
+                Some(a) => {
+                    let globs: Vec<String> =
+                        a.iter().filter_map(|g| g.as_str().map(str::to_string)).collect();
+                    match graph::glob_matcher(&state.root, &globs) {
+                        Ok(m) => Some(m),
+                        Err(e) => return Ok(tool_error(format!("Invalid entrypoint glob: {}", e))),
+                    }
+                }
+                None => None,
+            };
+            let only = match file_path.as_str() {
+                "" => None,
+                "." => Some(".".to_string()),
+                path => match state.key_for(path) {
+                    Some(key) => Some(key),
+                    None => return Ok(tool_error(format!("Path outside the project: {}", path))),
+                },
+            };
+            let report = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || {
+                    let _gate = state.batch_gate.read().unwrap();
+                    let report = metrics::compute(
+                        &state,
+                        entrypoints.as_ref(),
+                        by_directory,
+                        only.as_deref(),
+                        &sort_by,
+                        limit,
+                    );
+                    serde_json::to_string(&report)
+                }
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(tool_text(report.unwrap_or_default()))
+        }
+        "check_architecture" => {
+            let rule = args.get("rule").and_then(|r| r.as_str());
+            let key = state.key_for(&file_path);
+            let violations: Vec<_> = architecture::violations(state)
+                .into_iter()
+                .filter(|v| file_path.is_empty() || Some(&v.file) == key.as_ref())
+                .filter(|v| rule.is_none_or(|r| v.rule == r))
+                .collect();
+            let rules = state.architecture.read().unwrap();
+            let mut report = json!({
+                "rules": rules.rule_count(),
+                "violations": violations,
+            });
+            if let Some(error) = &rules.error {
+                report["error"] = json!(error);
+            }
+            Ok(tool_text(report.to_string()))
+        }
+        "get_test_reachability" => {
+            let globs: Vec<String> = match args.get("test_globs").and_then(|g| g.as_array()) {
+                Some(a) => a
+                    .iter()
+                    .filter_map(|g| g.as_str().map(str::to_string))
+                    .collect(),
+                None => coverage::DEFAULT_TEST_GLOBS
+                    .iter()
+                    .map(|g| g.to_string())
+                    .collect(),
+            };
+            let test_globs = match graph::glob_matcher(&state.root, &globs) {
+                Ok(m) => m,
+                Err(e) => return Ok(tool_error(format!("Invalid test glob: {}", e))),
+            };
+            let key = if file_path.is_empty() {
+                None
+            } else {
+                match state.key_for(&file_path) {
+                    Some(key) if state.skeleton_graph.contains_key(&key) => Some(key),
+                    _ => return Ok(tool_error(format!("File not found in graph: {}", file_path))),
+                }
+            };
+            let report = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || {
+                    let _gate = state.batch_gate.read().unwrap();
+                    let map = coverage::TestMap::build(&state, &test_globs);
+                    match key {
+                        Some(key) => serde_json::to_string(&map.file(&state, &key)),
+                        None => serde_json::to_string(&map.summary(&state)),
+                    }
+                }
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(tool_text(report.unwrap_or_default()))
+        }
+        "list_routes" => {
+            let framework = args.get("framework").and_then(|s| s.as_str());
+            let key = if file_path.is_empty() {
+                None
+            } else {
+                match state.key_for(&file_path) {
+                    Some(key) if state.skeleton_graph.contains_key(&key) => Some(key),
+                    _ => return Ok(tool_error(format!("File not found in graph: {}", file_path))),
+                }
+            };
+            let mut found = routes::all_routes(state, key.as_deref());
+            if let Some(framework) = framework {
+                found.retain(|r| r.route.framework == framework);
+            }
+            Ok(tool_text(serde_json::to_string(&found).unwrap_or_default()))
+        }
+        "list_components" => {
+            let key = if file_path.is_empty() {
+                None
+            } else {
+                match state.key_for(&file_path) {
+                    Some(key) if state.skeleton_graph.contains_key(&key) => Some(key),
+                    _ => return Ok(tool_error(format!("File not found in graph: {}", file_path))),
+                }
+            };
+            // Imported props types are parsed from their declaring file.
+            let found = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || symbols::components(&state, key.as_deref())
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(tool_text(serde_json::to_string(&found).unwrap_or_default()))
+        }
+        "find_dead_exports" => {
+            let globs: Vec<String> = args
+                .get("entrypoints")
+                .and_then(|g| g.as_array())
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|g| g.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let entrypoints = match graph::glob_matcher(&state.root, &globs) {
+                Ok(m) => m,
+                Err(e) => return Ok(tool_error(format!("Invalid entrypoint glob: {}", e))),
+            };
+            // Confirming in-file usage parses files; keep it off the reactor.
+            let dead = tokio::task::spawn_blocking({
+                let state = state.clone();
+                move || symbols::dead_exports(&state, &entrypoints)
+            })
+            .await
+            .map_err(|e| json!({"code": -32603, "message": format!("task failed: {}", e)}))?;
+            Ok(tool_text(serde_json::to_string(&dead).unwrap_or_default()))
+        }
         other => Err(json!({
             "code": -32602,
             "message": format!("Unknown tool: {}", other)
@@ -391,7 +1108,7 @@ pub async fn handle_request(state: &Arc<AppState>, req: Request) -> Option<Respo
         "initialize" => Ok(handle_initialize(req.params.as_ref())),
         "ping" => Ok(json!({})),
         "resources/list" => Ok(handle_resources_list(state)),
-        "resources/read" => handle_resources_read(state, req.params.as_ref()),
+        "resources/read" => handle_resources_read(state, req.params.as_ref()).await,
         "resources/subscribe" => handle_subscription(state, req.params.as_ref(), true),
         "resources/unsubscribe" => handle_subscription(state, req.params.as_ref(), false),
         "tools/list" => Ok(tools_list()),
@@ -2,8 +2,11 @@
 //!
 //! `FsResolver` handles relative specifiers against the importing file and
 //! classifies bare specifiers as external packages. tsconfig `paths` aliases
-//! are out of scope for now — implement a new `Resolver` for them.
+//! are out of scope for now — implement a new `Resolver` for them. The
+//! watcher tracks the config files listed by `is_resolver_config` and calls
+//! `Resolver::invalidate` before re-resolving the whole graph when one changes.
 
+use std::collections::HashSet;
 use std::path::Path;
 
 use crate::graph::{canonical_key, is_skeleton_target};
@@ -20,6 +23,20 @@ pub enum Resolution {
 
 pub trait Resolver: Send + Sync {
     fn resolve(&self, root: &Path, importer_key: &str, spec: &str) -> Resolution;
+
+    /// Drop anything derived from config files; called when one changes.
+    fn invalidate(&self) {}
+}
+
+/// True for files whose contents can change how specifiers resolve:
+/// `package.json` (workspace manifests) and `tsconfig*.json`/`jsconfig.json`.
+pub fn is_resolver_config(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name == "package.json"
+        || name == "jsconfig.json"
+        || (name.starts_with("tsconfig") && name.ends_with(".json"))
 }
 
 pub struct FsResolver;
@@ -36,6 +53,68 @@ fn package_name(spec: &str) -> String {
     }
 }
 
+/// `./x`, `../x`, `.` or `..`: resolved against the importing file.
+pub fn is_relative(spec: &str) -> bool {
+    spec.starts_with("./") || spec.starts_with("../") || spec == "." || spec == ".."
+}
+
+/// Relative-specifier resolution shared by the resolvers; `exists` decides
+/// whether a candidate key is a source file.
+fn resolve_with(
+    root: &Path,
+    importer_key: &str,
+    spec: &str,
+    exists: impl Fn(&str) -> bool,
+) -> Resolution {
+    if !is_relative(spec) {
+        return Resolution::External(package_name(spec));
+    }
+
+    let base = Path::new(importer_key)
+        .parent()
+        .unwrap_or_else(|| Path::new(""));
+    let raw = base.join(spec);
+    let raw_str = raw.to_string_lossy();
+
+    let candidates = [
+        format!("{}.ts", raw_str),
+        format!("{}.tsx", raw_str),
+        format!("{}/index.ts", raw_str),
+        format!("{}/index.tsx", raw_str),
+        raw_str.to_string(),
+    ];
+
+    for cand in &candidates {
+        if let Some(key) = canonical_key(root, Path::new(cand))
+            && exists(&key)
+        {
+            return Resolution::Internal(key);
+        }
+    }
+    Resolution::Unresolved
+}
+
+impl Resolver for FsResolver {
+    fn resolve(&self, root: &Path, importer_key: &str, spec: &str) -> Resolution {
+        resolve_with(root, importer_key, spec, |key| {
+            let abs = root.join(key);
+            abs.is_file() && is_skeleton_target(&abs)
+        })
+    }
+}
+
+/// Resolves against a fixed set of graph keys instead of the filesystem, for
+/// graphs built from a git revision.
+pub struct KeySetResolver {
+    pub keys: HashSet<String>,
+}
+
+impl Resolver for KeySetResolver {
+    fn resolve(&self, root: &Path, importer_key: &str, spec: &str) -> Resolution {
+        resolve_with(root, importer_key, spec, |key| self.keys.contains(key))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,37 +158,18 @@ mod tests {
             r.resolve(&root, "src/a.ts", "@scope/pkg/sub"),
             Resolution::External("@scope/pkg".into())
         );
-    }
-}
 
-impl Resolver for FsResolver {
-    fn resolve(&self, root: &Path, importer_key: &str, spec: &str) -> Resolution {
-        if !spec.starts_with("./") && !spec.starts_with("../") && spec != "." && spec != ".." {
-            return Resolution::External(package_name(spec));
-        }
-
-        let base = Path::new(importer_key)
-            .parent()
-            .unwrap_or_else(|| Path::new(""));
-        let raw = base.join(spec);
-        let raw_str = raw.to_string_lossy();
-
-        let candidates = [
-            format!("{}.ts", raw_str),
-            format!("{}.tsx", raw_str),
-            format!("{}/index.ts", raw_str),
-            format!("{}/index.tsx", raw_str),
-            raw_str.to_string(),
-        ];
-
-        for cand in &candidates {
-            if let Some(key) = canonical_key(root, Path::new(cand)) {
-                let abs = root.join(&key);
-                if abs.is_file() && is_skeleton_target(&abs) {
-                    return Resolution::Internal(key);
-                }
-            }
-        }
-        Resolution::Unresolved
+        let keys = KeySetResolver {
+            keys: HashSet::from(["src/gone.ts".to_string(), "src/lib/index.tsx".to_string()]),
+        };
+        assert_eq!(
+            keys.resolve(&root, "src/a.ts", "./gone"),
+            Resolution::Internal("src/gone.ts".into())
+        );
+        assert_eq!(
+            keys.resolve(&root, "src/a.ts", "./utils/api"),
+            Resolution::Unresolved,
+            "only the key set counts, not the working tree"
+        );
     }
 }
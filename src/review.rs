@@ -0,0 +1,139 @@
+//! Review context for the current branch: the files it touched since the
+//! merge base (plus uncommitted and untracked work), the symbols their diff
+//! hunks overlap, and the files that import them.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::git::{self, ChangedFile};
+use crate::graph::{is_skeleton_target, AppState};
+use crate::skeleton::FileSkeleton;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReviewFile {
+    pub file: String,
+    /// added | modified | deleted | untracked
+    pub status: String,
+    /// Symbols (`Class.method` for members) whose lines a hunk touches.
+    pub changed_symbols: Vec<String>,
+    /// Current skeleton; absent for deleted files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skeleton: Option<FileSkeleton>,
+}
+
+/// An unchanged file importing changed ones.
+#[derive(Serialize, Debug, Clone)]
+pub struct Dependent {
+    pub file: String,
+    /// The changed files it imports.
+    pub imports: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ReviewContext {
+    pub base: String,
+    pub merge_base: String,
+    pub files: Vec<ReviewFile>,
+    pub dependents: Vec<Dependent>,
+    /// Changed paths outside the graph (styles, configs, docs).
+    pub other_changes: Vec<String>,
+}
+
+/// Names of the symbols in `node` whose line range overlaps a hunk. Files
+/// without hunks (untracked, fully added) count as changed throughout.
+fn changed_symbols(node: &FileSkeleton, changed: &ChangedFile) -> Vec<String> {
+    let whole = changed.hunks.is_empty() || changed.status == "added";
+    let mut names: Vec<String> = node
+        .symbols
+        .iter()
+        .filter(|s| {
+            whole
+                || changed
+                    .hunks
+                    .iter()
+                    .any(|(start, end)| s.location.start_line <= *end && *start <= s.location.end_line)
+        })
+        .map(|s| s.name.clone())
+        .collect();
+    names.dedup();
+    names
+}
+
+pub fn context(state: &AppState, base: &str) -> Result<ReviewContext> {
+    let merge_base = git::merge_base(&state.root, base)?;
+    let mut files = Vec::new();
+    let mut other_changes = Vec::new();
+    for changed in git::changed_files(&state.root, &merge_base)? {
+        let Some(key) = state.key_for(&changed.path) else {
+            continue;
+        };
+        let node = state.skeleton_graph.get(&key).map(|n| n.clone());
+        match node {
+            Some(node) => files.push(ReviewFile {
+                changed_symbols: changed_symbols(&node, &changed),
+                file: key,
+                status: changed.status,
+                skeleton: Some(node),
+            }),
+            None if changed.status == "deleted" && is_skeleton_target(&state.abs_path(&key)) => {
+                files.push(ReviewFile {
+                    file: key,
+                    status: changed.status,
+                    changed_symbols: Vec::new(),
+                    skeleton: None,
+                })
+            }
+            None => other_changes.push(key),
+        }
+    }
+
+    let touched: HashSet<&str> = files.iter().map(|f| f.file.as_str()).collect();
+    let mut dependents: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for file in &files {
+        for importer in state.dependents_of(&file.file) {
+            if !touched.contains(importer.as_str()) {
+                dependents.entry(importer).or_default().push(file.file.clone());
+            }
+        }
+    }
+    Ok(ReviewContext {
+        base: base.to_string(),
+        merge_base,
+        files,
+        dependents: dependents
+            .into_iter()
+            .map(|(file, imports)| Dependent { file, imports })
+            .collect(),
+        other_changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skeleton::skeletonize_source;
+    use std::path::Path;
+
+    #[test]
+    fn hunks_select_the_symbols_they_overlap() {
+        let node = skeletonize_source(
+            "export function a() {\n  return 1;\n}\n\nexport class B {\n  m() {}\n  n() {}\n}\n",
+            Path::new("x.ts"),
+        )
+        .unwrap();
+        let changed = |status: &str, hunks: Vec<(u32, u32)>| ChangedFile {
+            path: "x.ts".into(),
+            status: status.into(),
+            hunks,
+        };
+        assert_eq!(changed_symbols(&node, &changed("modified", vec![(2, 2)])), ["a"]);
+        assert_eq!(changed_symbols(&node, &changed("modified", vec![(7, 7)])), ["B", "B.n"]);
+        assert_eq!(
+            changed_symbols(&node, &changed("modified", vec![(3, 4)])),
+            ["a"],
+            "a deletion right after a symbol's last line still touches it"
+        );
+        assert_eq!(changed_symbols(&node, &changed("untracked", vec![])), ["a", "B", "B.m", "B.n"]);
+    }
+}
@@ -0,0 +1,257 @@
+//! Structural diff between two revisions of the skeleton graph.
+//!
+//! A revision's graph is rebuilt from blobs in the local repository: the
+//! same `.ts`/`.tsx` files the live graph would track, skeletonized with
+//! `skeletonize_source` and resolved against the revision's own file set.
+//! The working tree side is the live graph itself.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use crate::git;
+use crate::graph::{import_cycles, is_skeleton_target, AppState};
+use crate::resolve::KeySetResolver;
+use crate::skeleton::{skeletonize_source, FileSkeleton, SymbolInfo};
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SymbolSummary {
+    pub name: String,
+    pub kind: String,
+    pub exported: bool,
+    pub signature: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SymbolChange {
+    pub before: SymbolSummary,
+    pub after: SymbolSummary,
+}
+
+/// Structural changes to one file; bodies are ignored.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct FileChange {
+    pub file: String,
+    /// added | removed | modified
+    pub status: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_symbols: Vec<SymbolSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_symbols: Vec<SymbolSummary>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_symbols: Vec<SymbolChange>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_imports: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_imports: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_packages: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub removed_packages: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct GraphDiff {
+    pub from: String,
+    pub to: String,
+    pub files: Vec<FileChange>,
+    /// Import cycles in `to` that no cycle in `from` contains.
+    pub new_cycles: Vec<Vec<String>>,
+    /// Import cycles in `from` that no cycle in `to` contains.
+    pub removed_cycles: Vec<Vec<String>>,
+}
+
+/// The graph as of `rev`, detached from the live one. Files the live graph
+/// would ignore are skipped, as are files that fail to parse.
+pub fn graph_at(state: &AppState, rev: &str) -> Result<AppState> {
+    let keys: Vec<String> = git::list_files(&state.root, rev)?
+        .into_iter()
+        .filter(|k| is_skeleton_target(Path::new(k)) && !state.is_ignored(&state.abs_path(k)))
+        .collect();
+    let mut nodes = Vec::new();
+    for (key, text) in git::show_files(&state.root, rev, &keys)? {
+        let Some(text) = text else {
+            continue;
+        };
+        match skeletonize_source(&text, Path::new(&key)) {
+            Ok(node) => nodes.push((key, node)),
+            Err(e) => tracing::warn!("{} at {}: skipping: {}", key, rev, e),
+        }
+    }
+    let mut graph = AppState::new(state.root.clone());
+    graph.resolver = Box::new(KeySetResolver {
+        keys: nodes.iter().map(|(k, _)| k.clone()).collect(),
+    });
+    for (key, node) in nodes {
+        graph.upsert(key, node);
+    }
+    Ok(graph)
+}
+
+fn summary(symbol: &SymbolInfo) -> SymbolSummary {
+    SymbolSummary {
+        name: symbol.name.clone(),
+        kind: symbol.kind.clone(),
+        exported: symbol.exported,
+        signature: symbol.signature.clone(),
+    }
+}
+
+/// Everything about a symbol's shape that a diff should surface.
+fn shape(symbol: &SymbolInfo) -> (bool, &str, &[String], Vec<&str>) {
+    (
+        symbol.exported,
+        symbol.signature.as_str(),
+        &symbol.modifiers,
+        symbol.overloads.iter().map(|o| o.signature.as_str()).collect(),
+    )
+}
+
+/// Symbols keyed by (name, kind), so a value and a type sharing a name stay
+/// apart.
+fn by_name(node: Option<&FileSkeleton>) -> BTreeMap<(&str, &str), &SymbolInfo> {
+    node.into_iter()
+        .flat_map(|n| &n.symbols)
+        .map(|s| ((s.name.as_str(), s.kind.as_str()), s))
+        .collect()
+}
+
+/// Items of `a` missing from `b`.
+fn missing(a: &[String], b: &[String]) -> Vec<String> {
+    a.iter().filter(|x| !b.contains(x)).cloned().collect()
+}
+
+fn file_change(key: &str, before: Option<&FileSkeleton>, after: Option<&FileSkeleton>) -> Option<FileChange> {
+    let status = match (before, after) {
+        (None, Some(_)) => "added",
+        (Some(_), None) => "removed",
+        _ => "modified",
+    };
+    let (old, new) = (by_name(before), by_name(after));
+    let mut change = FileChange {
+        file: key.to_string(),
+        status: status.to_string(),
+        ..FileChange::default()
+    };
+    for (id, symbol) in &new {
+        match old.get(id) {
+            None => change.added_symbols.push(summary(symbol)),
+            Some(prev) if shape(prev) != shape(symbol) => change.changed_symbols.push(SymbolChange {
+                before: summary(prev),
+                after: summary(symbol),
+            }),
+            Some(_) => {}
+        }
+    }
+    change.removed_symbols = old
+        .iter()
+        .filter(|(id, _)| !new.contains_key(*id))
+        .map(|(_, s)| summary(s))
+        .collect();
+
+    let field = |node: Option<&FileSkeleton>, f: fn(&FileSkeleton) -> &Vec<String>| {
+        node.map(|n| f(n).clone()).unwrap_or_default()
+    };
+    let (old_deps, new_deps) = (field(before, |n| &n.dependencies), field(after, |n| &n.dependencies));
+    change.added_imports = missing(&new_deps, &old_deps);
+    change.removed_imports = missing(&old_deps, &new_deps);
+    let (old_pkgs, new_pkgs) = (field(before, |n| &n.external_deps), field(after, |n| &n.external_deps));
+    change.added_packages = missing(&new_pkgs, &old_pkgs);
+    change.removed_packages = missing(&old_pkgs, &new_pkgs);
+
+    let touched = status != "modified"
+        || !change.added_symbols.is_empty()
+        || !change.removed_symbols.is_empty()
+        || !change.changed_symbols.is_empty()
+        || !change.added_imports.is_empty()
+        || !change.removed_imports.is_empty()
+        || !change.added_packages.is_empty()
+        || !change.removed_packages.is_empty();
+    touched.then_some(change)
+}
+
+/// Cycles of `a` not contained in any cycle of `b`.
+fn cycles_missing(a: &[Vec<String>], b: &[Vec<String>]) -> Vec<Vec<String>> {
+    a.iter()
+        .filter(|cycle| !b.iter().any(|other| cycle.iter().all(|k| other.contains(k))))
+        .cloned()
+        .collect()
+}
+
+/// Per-file structural changes from `before` to `after`, plus import cycles
+/// that appeared or disappeared. Files whose changes are body-only are left
+/// out.
+pub fn diff(before: &AppState, after: &AppState, from: &str, to: &str) -> GraphDiff {
+    let mut keys: Vec<String> = before
+        .skeleton_graph
+        .iter()
+        .chain(after.skeleton_graph.iter())
+        .map(|e| e.key().clone())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    keys.sort();
+    let files = keys
+        .iter()
+        .filter_map(|key| {
+            let old = before.skeleton_graph.get(key).map(|n| n.clone());
+            let new = after.skeleton_graph.get(key).map(|n| n.clone());
+            file_change(key, old.as_ref(), new.as_ref())
+        })
+        .collect();
+    let (old_cycles, new_cycles) = (import_cycles(before), import_cycles(after));
+    GraphDiff {
+        from: from.to_string(),
+        to: to.to_string(),
+        files,
+        new_cycles: cycles_missing(&new_cycles, &old_cycles),
+        removed_cycles: cycles_missing(&old_cycles, &new_cycles),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn graph(files: &[(&str, &str)]) -> AppState {
+        let mut state = AppState::new(PathBuf::from("/repo"));
+        state.resolver = Box::new(KeySetResolver {
+            keys: files.iter().map(|(k, _)| k.to_string()).collect(),
+        });
+        for (key, src) in files {
+            state.upsert(key.to_string(), skeletonize_source(src, Path::new(key)).unwrap());
+        }
+        state
+    }
+
+    #[test]
+    fn reports_symbol_import_and_cycle_changes_but_not_bodies() {
+        let before = graph(&[
+            ("src/a.ts", "import { b } from './b';\nexport function run(x: number) { return b; }\nexport const gone = 1;\n"),
+            ("src/b.ts", "export const b = 1;\n"),
+            ("src/quiet.ts", "export function f() { return 1; }\n"),
+            ("src/old.ts", "export type T = string;\n"),
+        ]);
+        let after = graph(&[
+            ("src/a.ts", "import { b } from './b';\nimport React from 'react';\nexport function run(x: string) { return b; }\nexport const added = 2;\n"),
+            ("src/b.ts", "import { run } from './a';\nexport const b = 1;\n"),
+            ("src/quiet.ts", "export function f() { return 2; }\n"),
+        ]);
+        let diff = diff(&before, &after, "main", "working tree");
+        let files: Vec<(&str, &str)> = diff.files.iter().map(|f| (f.file.as_str(), f.status.as_str())).collect();
+        assert_eq!(files, [("src/a.ts", "modified"), ("src/b.ts", "modified"), ("src/old.ts", "removed")]);
+
+        let a = &diff.files[0];
+        let names = |v: &[SymbolSummary]| v.iter().map(|s| s.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&a.added_symbols), ["added"]);
+        assert_eq!(names(&a.removed_symbols), ["gone"]);
+        assert_eq!(a.changed_symbols.len(), 1);
+        assert!(a.changed_symbols[0].after.signature.contains("x: string"));
+        assert_eq!(a.added_packages, ["react"]);
+        assert_eq!(diff.files[1].added_imports, ["src/a.ts"]);
+        assert_eq!(diff.new_cycles, vec![vec!["src/a.ts", "src/b.ts"]]);
+        assert!(diff.removed_cycles.is_empty());
+    }
+}
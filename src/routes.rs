@@ -0,0 +1,839 @@
+//! Framework route discovery.
+//!
+//! Two sources. Routes declared in code — React Router `<Route path>`
+//! elements and route-object arrays (`createBrowserRouter`, `useRoutes`,
+//! `RouteObject[]` bindings), and `app.get("/path", handler)`-style calls
+//! (Express, Fastify, Hono, Koa) — are collected per file before bodies are
+//! stripped and stored in the skeleton. File-system routes (Next.js `app/`
+//! and `pages/`, Remix `app/routes/`) are derived from graph keys at query
+//! time. Paths keep each framework's own parameter notation (`[id]`,
+//! `:id`).
+
+use oxc_ast::ast::*;
+use oxc_ast_visit::{walk, Visit};
+use oxc_span::Span;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::components::component_name;
+use crate::graph::AppState;
+use crate::skeleton::{FileSkeleton, Location};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteRecord {
+    pub path: String,
+    /// next-app | next-pages | remix | react-router | express | fastify |
+    /// hono | koa
+    pub framework: String,
+    /// page | endpoint
+    pub kind: String,
+    /// HTTP method for endpoints, when the route fixes one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Symbol handling the route as written (`UserPage`, `users.list`,
+    /// `GET`); `None` for inline handlers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+    #[serde(default)]
+    pub location: Location,
+}
+
+/// A route tagged with the graph key that declares it.
+#[derive(Serialize, Debug, Clone)]
+pub struct FileRoute {
+    pub file: String,
+    #[serde(flatten)]
+    pub route: RouteRecord,
+}
+
+const HTTP_METHODS: &[&str] = &["get", "post", "put", "patch", "delete", "options", "head", "all"];
+const ROUTER_FACTORIES: &[&str] = &[
+    "createBrowserRouter",
+    "createHashRouter",
+    "createMemoryRouter",
+    "createStaticRouter",
+    "useRoutes",
+];
+
+fn join(parent: &str, child: &str) -> String {
+    if child.starts_with('/') {
+        child.to_string()
+    } else if child.is_empty() {
+        parent.to_string()
+    } else if parent.ends_with('/') {
+        format!("{}{}", parent, child)
+    } else {
+        format!("{}/{}", parent, child)
+    }
+}
+
+fn string_value(expr: &Expression<'_>) -> Option<String> {
+    match expr.get_inner_expression() {
+        Expression::StringLiteral(s) => Some(s.value.to_string()),
+        Expression::TemplateLiteral(t) if t.expressions.is_empty() => {
+            t.quasis.first().map(|q| q.value.raw.to_string())
+        }
+        _ => None,
+    }
+}
+
+fn handler_name(expr: &Expression<'_>) -> Option<String> {
+    match expr.get_inner_expression() {
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::StaticMemberExpression(m) => match m.object.get_inner_expression() {
+            Expression::Identifier(obj) => Some(format!("{}.{}", obj.name, m.property.name)),
+            _ => None,
+        },
+        Expression::JSXElement(el) => component_name(&el.opening_element.name),
+        _ => None,
+    }
+}
+
+/// Server framework a module specifier provides route-registering
+/// factories for.
+fn framework_of(source: &str) -> Option<&'static str> {
+    match source {
+        "express" => Some("express"),
+        "fastify" => Some("fastify"),
+        "hono" => Some("hono"),
+        "@koa/router" | "koa-router" => Some("koa"),
+        _ if source.starts_with("hono/") => Some("hono"),
+        _ => None,
+    }
+}
+
+/// Local names bound to a server framework's module or router class, from
+/// top-level imports and `require` calls: `express`, `{ Router }` from
+/// express, `Fastify`, `{ Hono }`, `Router` from `@koa/router`.
+fn server_imports(program: &Program<'_>) -> HashMap<String, &'static str> {
+    let mut out = HashMap::new();
+    for stmt in &program.body {
+        match stmt {
+            Statement::ImportDeclaration(i) => {
+                let Some(framework) = framework_of(&i.source.value) else {
+                    continue;
+                };
+                for spec in i.specifiers.iter().flatten() {
+                    let local = match spec {
+                        ImportDeclarationSpecifier::ImportSpecifier(s)
+                            if matches!(s.imported.name().as_str(), "Router" | "Hono") =>
+                        {
+                            &s.local.name
+                        }
+                        ImportDeclarationSpecifier::ImportSpecifier(_) => continue,
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => &s.local.name,
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => &s.local.name,
+                    };
+                    out.insert(local.to_string(), framework);
+                }
+            }
+            Statement::VariableDeclaration(v) => {
+                for d in &v.declarations {
+                    let Some(Expression::CallExpression(call)) =
+                        d.init.as_ref().map(|i| i.get_inner_expression())
+                    else {
+                        continue;
+                    };
+                    let is_require =
+                        matches!(&call.callee, Expression::Identifier(id) if id.name == "require");
+                    let Some(framework) = call
+                        .arguments
+                        .first()
+                        .and_then(|a| a.as_expression())
+                        .and_then(string_value)
+                        .and_then(|s| framework_of(&s))
+                        .filter(|_| is_require)
+                    else {
+                        continue;
+                    };
+                    match &d.id {
+                        BindingPattern::BindingIdentifier(id) => {
+                            out.insert(id.name.to_string(), framework);
+                        }
+                        BindingPattern::ObjectPattern(obj) => {
+                            for prop in &obj.properties {
+                                if let (Some("Router" | "Hono"), BindingPattern::BindingIdentifier(id)) =
+                                    (prop.key.static_name().as_deref(), &prop.value)
+                                {
+                                    out.insert(id.name.to_string(), framework);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Framework of a server instance created by `init` from an imported
+/// factory: `express()`, `Router()` and `express.Router()` from express,
+/// `Fastify()`, `new Hono()`, `new Router()` from `@koa/router`.
+fn server_factory(init: &Expression<'_>, imports: &HashMap<String, &'static str>) -> Option<&'static str> {
+    let callee = match init.get_inner_expression() {
+        Expression::CallExpression(call) => &call.callee,
+        Expression::NewExpression(new) => &new.callee,
+        _ => return None,
+    };
+    match callee.get_inner_expression() {
+        Expression::Identifier(id) => imports.get(id.name.as_str()).copied(),
+        Expression::StaticMemberExpression(m) if m.property.name == "Router" => {
+            match m.object.get_inner_expression() {
+                Expression::Identifier(o) => imports.get(o.name.as_str()).copied(),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+struct RouteFinder<'b, 'a> {
+    /// Imported server factories; see `server_imports`.
+    imports: HashMap<String, &'static str>,
+    /// Bindings holding a server instance (`const app = express()`), with
+    /// its framework. Only calls on these are server routes, so client
+    /// calls such as `axios.post('/api/users', body)` are not.
+    servers: HashMap<String, &'static str>,
+    /// Top-level `const x = [...]` bindings, for `createBrowserRouter(routes)`.
+    arrays: HashMap<&'b str, &'b ArrayExpression<'a>>,
+    /// Full path of the enclosing `<Route>`.
+    prefix: Vec<String>,
+    found: Vec<(RouteRecord, Span)>,
+}
+
+impl<'b, 'a> RouteFinder<'b, 'a> {
+    fn parent(&self) -> String {
+        self.prefix.last().cloned().unwrap_or_else(|| "/".to_string())
+    }
+
+    fn page(&mut self, path: String, handler: Option<String>, span: Span) {
+        self.found.push((
+            RouteRecord {
+                path,
+                framework: "react-router".to_string(),
+                kind: "page".to_string(),
+                method: None,
+                handler,
+                location: Location::default(),
+            },
+            span,
+        ));
+    }
+
+    /// React Router route objects: `{ path, index, element, Component, children }`.
+    fn route_objects(&mut self, array: &ArrayExpression<'a>, parent: &str) {
+        for element in &array.elements {
+            let Some(Expression::ObjectExpression(obj)) = element.as_expression() else {
+                continue;
+            };
+            let mut path = None;
+            let mut index = false;
+            let mut handler = None;
+            let mut children = None;
+            for prop in &obj.properties {
+                let ObjectPropertyKind::ObjectProperty(p) = prop else {
+                    continue;
+                };
+                match p.key.static_name().as_deref() {
+                    Some("path") => path = string_value(&p.value),
+                    Some("index") => index = matches!(p.value, Expression::BooleanLiteral(ref b) if b.value),
+                    Some("element" | "Component") => handler = handler_name(&p.value),
+                    Some("children") => {
+                        if let Expression::ArrayExpression(a) = &p.value {
+                            children = Some(a);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let full = path.as_deref().map_or_else(|| parent.to_string(), |p| join(parent, p));
+            if path.is_some() || index {
+                self.page(full.clone(), handler, obj.span);
+            }
+            if let Some(children) = children {
+                self.route_objects(children, &full);
+            }
+        }
+    }
+
+    fn server_route(&mut self, call: &CallExpression<'a>, method: &str, framework: &'static str) {
+        let Some(path) = call
+            .arguments
+            .first()
+            .and_then(|a| a.as_expression())
+            .and_then(string_value)
+            .filter(|p| p.starts_with('/'))
+        else {
+            return;
+        };
+        if call.arguments.len() < 2 {
+            return;
+        }
+        let handler = call
+            .arguments
+            .last()
+            .and_then(|a| a.as_expression())
+            .and_then(handler_name);
+        self.endpoint(framework, path, Some(method.to_ascii_uppercase()), handler, call.span);
+    }
+
+    /// Fastify's `app.route({ method, url, handler })`.
+    fn route_options(&mut self, call: &CallExpression<'a>, framework: &'static str) {
+        let Some(Expression::ObjectExpression(obj)) =
+            call.arguments.first().and_then(|a| a.as_expression())
+        else {
+            return;
+        };
+        let (mut url, mut method, mut handler) = (None, None, None);
+        for prop in &obj.properties {
+            let ObjectPropertyKind::ObjectProperty(p) = prop else {
+                continue;
+            };
+            match p.key.static_name().as_deref() {
+                Some("url" | "path") => url = string_value(&p.value),
+                Some("method") => {
+                    method = match &p.value {
+                        Expression::ArrayExpression(a) => Some(
+                            a.elements
+                                .iter()
+                                .filter_map(|e| e.as_expression().and_then(string_value))
+                                .collect::<Vec<_>>()
+                                .join(","),
+                        ),
+                        other => string_value(other),
+                    }
+                }
+                Some("handler") => handler = handler_name(&p.value),
+                _ => {}
+            }
+        }
+        if let Some(url) = url {
+            self.endpoint(framework, url, method.map(|m| m.to_ascii_uppercase()), handler, call.span);
+        }
+    }
+
+    fn endpoint(
+        &mut self,
+        framework: &str,
+        path: String,
+        method: Option<String>,
+        handler: Option<String>,
+        span: Span,
+    ) {
+        self.found.push((
+            RouteRecord {
+                path,
+                framework: framework.to_string(),
+                kind: "endpoint".to_string(),
+                method,
+                handler,
+                location: Location::default(),
+            },
+            span,
+        ));
+    }
+}
+
+impl<'a> Visit<'a> for RouteFinder<'_, 'a> {
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        match it.callee.get_inner_expression() {
+            Expression::Identifier(id) if ROUTER_FACTORIES.contains(&id.name.as_str()) => {
+                match it.arguments.first().and_then(|a| a.as_expression()) {
+                    Some(Expression::ArrayExpression(a)) => self.route_objects(a, "/"),
+                    Some(Expression::Identifier(r)) => {
+                        if let Some(a) = self.arrays.remove(r.name.as_str()) {
+                            self.route_objects(a, "/");
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Expression::StaticMemberExpression(m) => {
+                let receiver = match m.object.get_inner_expression() {
+                    Expression::Identifier(obj) => self.servers.get(obj.name.as_str()).copied(),
+                    _ => None,
+                };
+                if let Some(framework) = receiver {
+                    let property = m.property.name.as_str();
+                    if HTTP_METHODS.contains(&property) {
+                        self.server_route(it, property, framework);
+                    } else if property == "route" {
+                        self.route_options(it, framework);
+                    }
+                }
+            }
+            _ => {}
+        }
+        walk::walk_call_expression(self, it);
+    }
+
+    fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+        if let (BindingPattern::BindingIdentifier(id), Some(framework)) =
+            (&it.id, it.init.as_ref().and_then(|i| server_factory(i, &self.imports)))
+        {
+            self.servers.insert(id.name.to_string(), framework);
+        }
+        walk::walk_variable_declarator(self, it);
+    }
+
+    fn visit_jsx_element(&mut self, it: &JSXElement<'a>) {
+        let opening = &it.opening_element;
+        if !matches!(&opening.name, JSXElementName::IdentifierReference(id) if id.name == "Route") {
+            walk::walk_jsx_element(self, it);
+            return;
+        }
+        let mut path = None;
+        let mut index = false;
+        let mut handler = None;
+        for attr in &opening.attributes {
+            let JSXAttributeItem::Attribute(a) = attr else {
+                continue;
+            };
+            let JSXAttributeName::Identifier(name) = &a.name else {
+                continue;
+            };
+            let value = match &a.value {
+                Some(JSXAttributeValue::StringLiteral(s)) => Some(s.value.to_string()),
+                Some(JSXAttributeValue::ExpressionContainer(c)) => {
+                    c.expression.as_expression().and_then(|e| match name.name.as_str() {
+                        "path" => string_value(e),
+                        _ => handler_name(e),
+                    })
+                }
+                _ => None,
+            };
+            match name.name.as_str() {
+                "path" => path = value,
+                "index" => {
+                    index = match &a.value {
+                        None => true,
+                        Some(JSXAttributeValue::ExpressionContainer(c)) => {
+                            matches!(c.expression.as_expression(), Some(Expression::BooleanLiteral(b)) if b.value)
+                        }
+                        _ => false,
+                    }
+                }
+                "element" | "Component" | "component" => handler = value,
+                _ => {}
+            }
+        }
+        let parent = self.parent();
+        let full = path.as_deref().map_or_else(|| parent.clone(), |p| join(&parent, p));
+        if path.is_some() || index {
+            self.page(full.clone(), handler, it.span);
+        }
+        self.prefix.push(full);
+        walk::walk_jsx_element(self, it);
+        self.prefix.pop();
+    }
+}
+
+/// Routes declared in code, in source order, with the span of each
+/// declaration.
+pub fn collect(program: &Program<'_>) -> Vec<(RouteRecord, Span)> {
+    let mut arrays = HashMap::new();
+    let mut annotated = Vec::new();
+    for stmt in &program.body {
+        let decl = match stmt {
+            Statement::ExportNamedDeclaration(e) => e.declaration.as_ref(),
+            _ => stmt.as_declaration(),
+        };
+        let Some(Declaration::VariableDeclaration(v)) = decl else {
+            continue;
+        };
+        for d in &v.declarations {
+            if let (BindingPattern::BindingIdentifier(id), Some(Expression::ArrayExpression(a))) =
+                (&d.id, d.init.as_ref().map(|i| i.get_inner_expression()))
+            {
+                // `const routes: RouteObject[] = [...]` is a route table even
+                // when the router is created in another file.
+                let is_route_table = d.type_annotation.as_ref().is_some_and(|t| {
+                    matches!(&t.type_annotation, TSType::TSArrayType(arr)
+                        if matches!(&arr.element_type, TSType::TSTypeReference(r)
+                            if matches!(&r.type_name, TSTypeName::IdentifierReference(n) if n.name == "RouteObject")))
+                });
+                if is_route_table {
+                    annotated.push(&**a);
+                } else {
+                    arrays.insert(id.name.as_str(), &**a);
+                }
+            }
+        }
+    }
+    let mut finder = RouteFinder {
+        imports: server_imports(program),
+        servers: HashMap::new(),
+        arrays,
+        prefix: Vec::new(),
+        found: Vec::new(),
+    };
+    for a in annotated {
+        finder.route_objects(a, "/");
+    }
+    finder.visit_program(program);
+    finder.found
+}
+
+/// True when the segments before a convention directory are a project root:
+/// nothing, `src`, or a workspace package (`apps/web`, `packages/ui/src`).
+fn project_prefix(prefix: &[&str]) -> bool {
+    match prefix {
+        [] | ["src"] => true,
+        [group, _] | [group, _, "src"] => matches!(*group, "apps" | "packages"),
+        _ => false,
+    }
+}
+
+/// Remix flat-route file name (without extension) as a URL path.
+fn remix_path(name: &str) -> String {
+    let mut segments = Vec::new();
+    for seg in name.split('.') {
+        if seg == "_index" || seg == "route" || seg.starts_with('_') {
+            continue;
+        }
+        let seg = seg.strip_suffix('_').unwrap_or(seg);
+        segments.push(if seg == "$" {
+            "*".to_string()
+        } else if let Some(param) = seg.strip_prefix("($").and_then(|s| s.strip_suffix(')')) {
+            format!(":{}?", param)
+        } else if let Some(param) = seg.strip_prefix('$') {
+            format!(":{}", param)
+        } else {
+            seg.to_string()
+        });
+    }
+    format!("/{}", segments.join("/"))
+}
+
+fn url(segments: &[&str]) -> String {
+    format!("/{}", segments.join("/"))
+}
+
+/// The default export's local name and location.
+fn default_export(node: &FileSkeleton) -> Option<(String, Location)> {
+    let local = node
+        .export_table
+        .iter()
+        .find(|e| e.name == "default" && e.source.is_none())?
+        .local
+        .clone()?;
+    let location = node
+        .symbols
+        .iter()
+        .find(|s| s.name == local)
+        .map(|s| s.location)
+        .unwrap_or_default();
+    Some((local, location))
+}
+
+fn exported_symbol(node: &FileSkeleton, name: &str) -> Option<Location> {
+    node.symbols
+        .iter()
+        .find(|s| s.name == name && s.exported)
+        .map(|s| s.location)
+}
+
+fn file_route(
+    path: String,
+    framework: &str,
+    kind: &str,
+    method: Option<&str>,
+    handler: Option<(String, Location)>,
+) -> RouteRecord {
+    let (handler, location) = handler.map_or((None, Location::default()), |(h, l)| (Some(h), l));
+    RouteRecord {
+        path,
+        framework: framework.to_string(),
+        kind: kind.to_string(),
+        method: method.map(str::to_string),
+        handler,
+        location,
+    }
+}
+
+/// Routes implied by a file's location under a framework's routing
+/// directory.
+pub fn file_routes(key: &str, node: &FileSkeleton) -> Vec<RouteRecord> {
+    let segments: Vec<&str> = key.split('/').collect();
+    let Some((file, dirs)) = segments.split_last() else {
+        return Vec::new();
+    };
+    let Some((stem, _)) = file.rsplit_once('.') else {
+        return Vec::new();
+    };
+    if stem.ends_with(".d") {
+        return Vec::new();
+    }
+    let mut out = Vec::new();
+
+    // Remix: app/routes/<flat.name>.tsx or app/routes/<flat.name>/route.tsx
+    if let Some(i) = dirs.windows(2).position(|w| w == ["app", "routes"])
+        && project_prefix(&dirs[..i])
+    {
+        let name = match &dirs[i + 2..] {
+            [] => stem,
+            [folder] if stem == "route" => *folder,
+            _ => return out,
+        };
+        let path = remix_path(name);
+        if let Some(default) = default_export(node) {
+            out.push(file_route(path, "remix", "page", None, Some(default)));
+        } else {
+            for (export, method) in [("loader", "GET"), ("action", "POST")] {
+                if let Some(location) = exported_symbol(node, export) {
+                    out.push(file_route(
+                        path.clone(),
+                        "remix",
+                        "endpoint",
+                        Some(method),
+                        Some((export.to_string(), location)),
+                    ));
+                }
+            }
+        }
+        return out;
+    }
+
+    // Next.js app router: app/**/page.tsx and app/**/route.ts
+    if let Some(i) = dirs.iter().position(|d| *d == "app")
+        && project_prefix(&dirs[..i])
+        && matches!(stem, "page" | "route")
+    {
+        let route_dirs = &dirs[i + 1..];
+        if route_dirs.iter().any(|d| d.starts_with('_')) {
+            return out;
+        }
+        let visible: Vec<&str> = route_dirs
+            .iter()
+            .copied()
+            // Route groups `(marketing)` and parallel slots `@modal` are not
+            // part of the URL.
+            .filter(|d| !(d.starts_with('@') || d.starts_with('(') && d.ends_with(')')))
+            .collect();
+        let path = url(&visible);
+        if stem == "page" {
+            out.push(file_route(path, "next-app", "page", None, default_export(node)));
+        } else {
+            for method in ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"] {
+                if let Some(location) = exported_symbol(node, method) {
+                    out.push(file_route(
+                        path.clone(),
+                        "next-app",
+                        "endpoint",
+                        Some(method),
+                        Some((method.to_string(), location)),
+                    ));
+                }
+            }
+        }
+        return out;
+    }
+
+    // Next.js pages router: pages/**/*.tsx, pages/api/** as endpoints.
+    if let Some(i) = dirs.iter().position(|d| *d == "pages")
+        && project_prefix(&dirs[..i])
+        && !stem.starts_with('_')
+    {
+        let mut route: Vec<&str> = dirs[i + 1..].to_vec();
+        if stem != "index" {
+            route.push(stem);
+        }
+        let kind = if route.first() == Some(&"api") { "endpoint" } else { "page" };
+        out.push(file_route(url(&route), "next-pages", kind, None, default_export(node)));
+    }
+    out
+}
+
+/// Every route in the graph (or in `key` only), sorted by path then file.
+pub fn all_routes(state: &AppState, key: Option<&str>) -> Vec<FileRoute> {
+    let mut out = Vec::new();
+    let mut add = |file: &str, node: &FileSkeleton| {
+        for route in file_routes(file, node).into_iter().chain(node.routes.iter().cloned()) {
+            out.push(FileRoute {
+                file: file.to_string(),
+                route,
+            });
+        }
+    };
+    match key {
+        Some(k) => {
+            if let Some(node) = state.skeleton_graph.get(k) {
+                add(k, &node);
+            }
+        }
+        None => {
+            for entry in state.skeleton_graph.iter() {
+                add(entry.key(), entry.value());
+            }
+        }
+    }
+    out.sort_by(|a, b| {
+        (&a.route.path, &a.route.method, &a.file, a.route.location.start)
+            .cmp(&(&b.route.path, &b.route.method, &b.file, b.route.location.start))
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skeleton::skeletonize_source;
+    use std::path::Path;
+
+    fn summary(routes: &[RouteRecord]) -> Vec<(String, String, Option<String>, Option<String>)> {
+        routes
+            .iter()
+            .map(|r| (r.framework.clone(), r.path.clone(), r.method.clone(), r.handler.clone()))
+            .collect()
+    }
+
+    fn row(framework: &str, path: &str, method: Option<&str>, handler: Option<&str>) -> (String, String, Option<String>, Option<String>) {
+        (framework.into(), path.into(), method.map(Into::into), handler.map(Into::into))
+    }
+
+    #[test]
+    fn react_router_elements_and_route_objects_nest_paths() {
+        let src = r#"
+import { createBrowserRouter, Routes, Route } from 'react-router-dom';
+const routes = [
+  { path: '/', element: <Layout />, children: [
+    { index: true, element: <Home /> },
+    { path: 'users/:id', Component: UserPage },
+    { path: '/settings', lazy: () => import('./settings') },
+  ] },
+];
+export const router = createBrowserRouter(routes);
+export function App() {
+  return (
+    <Routes>
+      <Route path="/admin" element={<Admin />}>
+        <Route path="audit" element={<Audit.Log />} />
+        <Route index={false} element={<Hidden />} />
+        <Route element={<Guard />}><Route path="keys" element={<Keys />} /></Route>
+      </Route>
+    </Routes>
+  );
+}
+"#;
+        let ir = skeletonize_source(src, Path::new("App.tsx")).unwrap();
+        assert_eq!(
+            summary(&ir.routes),
+            [
+                row("react-router", "/", None, Some("Layout")),
+                row("react-router", "/", None, Some("Home")),
+                row("react-router", "/users/:id", None, Some("UserPage")),
+                row("react-router", "/settings", None, None),
+                row("react-router", "/admin", None, Some("Admin")),
+                row("react-router", "/admin/audit", None, Some("Audit.Log")),
+                row("react-router", "/admin/keys", None, Some("Keys")),
+            ]
+        );
+        assert_eq!(ir.routes[0].location.start_line, 4);
+    }
+
+    #[test]
+    fn server_route_calls_record_method_and_handler() {
+        let src = r#"
+import Fastify from 'fastify';
+const app = Fastify();
+app.get('/users', users.list);
+app.post(`/users/:id`, auth, updateUser);
+app.delete('/users/:id', async (req, reply) => {});
+app.route({ method: ['GET', 'HEAD'], url: '/health', handler: health });
+cache.get('key', fallback);
+"#;
+        let ir = skeletonize_source(src, Path::new("server.ts")).unwrap();
+        assert_eq!(
+            summary(&ir.routes),
+            [
+                row("fastify", "/users", Some("GET"), Some("users.list")),
+                row("fastify", "/users/:id", Some("POST"), Some("updateUser")),
+                row("fastify", "/users/:id", Some("DELETE"), None),
+                row("fastify", "/health", Some("GET,HEAD"), Some("health")),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_calls_on_server_instances_are_routes() {
+        let src = r#"
+import axios from 'axios';
+import { api } from './api';
+export async function save(u) {
+  await axios.post('/api/users', u);
+  await api.get('/api/users/:id', { params });
+}
+"#;
+        let ir = skeletonize_source(src, Path::new("client.ts")).unwrap();
+        assert!(ir.routes.is_empty(), "{:?}", summary(&ir.routes));
+
+        let src = r#"
+const express = require('express');
+const app = express();
+const router = express.Router();
+router.get('/users', listUsers);
+app.use('/api', router);
+http.post('/track', payload);
+"#;
+        let ir = skeletonize_source(src, Path::new("server.js")).unwrap();
+        assert_eq!(summary(&ir.routes), [row("express", "/users", Some("GET"), Some("listUsers"))]);
+
+        let src = r#"
+import express, { Router as R } from 'express';
+import axios from 'axios';
+import { Router } from 'some-client';
+import KoaRouter from '@koa/router';
+const users = R();
+const client = Router();
+const koa = new KoaRouter();
+users.get('/users', listUsers);
+koa.post('/items', createItem);
+client.get('/remote', onRemote);
+axios.get('/api/users', { params: {} });
+"#;
+        let ir = skeletonize_source(src, Path::new("server.ts")).unwrap();
+        assert_eq!(
+            summary(&ir.routes),
+            [
+                row("express", "/users", Some("GET"), Some("listUsers")),
+                row("koa", "/items", Some("POST"), Some("createItem")),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_system_conventions_map_keys_to_paths() {
+        let page = skeletonize_source("export default function UserPage() { return null; }\n", Path::new("x.tsx")).unwrap();
+        let handlers = skeletonize_source(
+            "export async function GET() {}\nexport const POST = async () => {};\nexport const helper = 1;\n",
+            Path::new("x.ts"),
+        )
+        .unwrap();
+        let resource = skeletonize_source("export const loader = () => null;\n", Path::new("x.ts")).unwrap();
+        let cases: Vec<(&str, &FileSkeleton, Vec<_>)> = vec![
+            ("src/app/(shop)/users/[id]/page.tsx", &page, vec![row("next-app", "/users/[id]", None, Some("UserPage"))]),
+            ("app/page.tsx", &page, vec![row("next-app", "/", None, Some("UserPage"))]),
+            (
+                "apps/web/app/api/users/route.ts",
+                &handlers,
+                vec![row("next-app", "/api/users", Some("GET"), Some("GET")), row("next-app", "/api/users", Some("POST"), Some("POST"))],
+            ),
+            ("app/_lib/page.tsx", &page, vec![]),
+            ("src/pages/blog/index.tsx", &page, vec![row("next-pages", "/blog", None, Some("UserPage"))]),
+            ("pages/_app.tsx", &page, vec![]),
+            ("src/components/pages/Home.tsx", &page, vec![]),
+            ("app/routes/users.$id.tsx", &page, vec![row("remix", "/users/:id", None, Some("UserPage"))]),
+            ("app/routes/_auth.login/route.tsx", &page, vec![row("remix", "/login", None, Some("UserPage"))]),
+            ("app/routes/_index.tsx", &page, vec![row("remix", "/", None, Some("UserPage"))]),
+            ("app/routes/api.feed.ts", &resource, vec![row("remix", "/api/feed", Some("GET"), Some("loader"))]),
+        ];
+        for (key, node, expected) in cases {
+            assert_eq!(summary(&file_routes(key, node)), expected, "{}", key);
+        }
+        let api = file_routes("pages/api/users.ts", &page);
+        assert_eq!(api[0].kind, "endpoint");
+    }
+}
@@ -1,15 +1,21 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use oxc_allocator::Allocator;
 use oxc_ast::ast::*;
-use oxc_ast_visit::VisitMut;
+use oxc_ast_visit::{walk, Visit, VisitMut};
 use oxc_codegen::{Codegen, Gen};
 use oxc_parser::Parser;
 use oxc_span::{GetSpan, SourceType, Span};
 use oxc_syntax::scope::ScopeFlags;
 
+use crate::calls::{self, CallEdge, ImportedCall};
+use crate::components::{self, ComponentRecord};
+use crate::routes::{self, RouteRecord};
+use crate::types;
+
 // --- IR STRUCTURES ---
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -23,10 +29,30 @@ pub struct FileSkeleton {
     pub symbols: Vec<SymbolInfo>,
     /// Structured import records (source specifier as written).
     pub import_records: Vec<ImportRecord>,
+    /// Every public export name of the file and what it refers to.
+    #[serde(default)]
+    pub export_table: Vec<ExportEntry>,
     /// Resolved graph keys this file imports (filled in by the graph layer).
     pub dependencies: Vec<String>,
+    /// Per resolved dependency, the export names consumed from it (`*` for
+    /// namespace imports and `export *`; empty for side-effect imports).
+    #[serde(default)]
+    pub symbol_dependencies: BTreeMap<String, Vec<String>>,
     /// Bare (package) specifiers this file imports.
     pub external_deps: Vec<String>,
+    /// Syntactic call edges between this file's top-level functions, arrow
+    /// bindings and methods.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_graph: Vec<CallEdge>,
+    /// Calls from this file's nodes to imported functions, unresolved.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imported_calls: Vec<ImportedCall>,
+    /// React components (`.tsx` only) with props, hooks and rendered children.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ComponentRecord>,
+    /// Routes declared in code (React Router configs, `app.get("/x", h)`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<RouteRecord>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -34,16 +60,101 @@ pub struct ImportRecord {
     pub source: String,
     pub names: Vec<String>,
     pub type_only: bool,
+    /// Span of the import / re-export statement.
+    #[serde(default)]
+    pub location: Location,
+    /// Per-name detail behind `names`, in the same order.
+    #[serde(default)]
+    pub bindings: Vec<ImportBinding>,
+    /// `import("./x")` with a string-literal specifier, found anywhere in the
+    /// file; binds the whole namespace (`*`).
+    #[serde(default)]
+    pub dynamic: bool,
+    /// Graph key `source` resolves to (filled in by the graph layer); `None`
+    /// for packages and unresolved specifiers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// One name an import or re-export statement binds.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImportBinding {
+    /// Name taken from the source module: an export name, `default`, or `*`
+    /// for namespace imports and `export *`.
+    pub imported: String,
+    /// Name it is bound to here; for re-exports, the name exported onwards.
+    pub local: String,
+    /// `import type` / `import { type X }`.
+    pub type_only: bool,
+}
+
+/// One public export name. Exactly one of `local` (this file's own binding,
+/// possibly an import re-exported by name) or `source` (a re-export) is set.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ExportEntry {
+    /// Name importers see: `default` for the default export, `*` for an
+    /// `export * from` that forwards every name of `source`.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    /// Re-export specifier as written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Name taken from `source`: `default`, a named export, or `*` for
+    /// `export * as ns` / `export *`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported: Option<String>,
+    pub type_only: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct SymbolInfo {
     pub name: String,
     /// function | arrow_function | class | method | interface | type | enum |
-    /// variable | component
+    /// variable | component | reexport
     pub kind: String,
     pub exported: bool,
     pub signature: String,
+    /// Span of the declaration, matching what `get_implementation` slices.
+    /// For an overload set it covers every signature plus the implementation.
+    #[serde(default)]
+    pub location: Location,
+    /// Class members only: method | getter | setter | constructor.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method_kind: Option<String>,
+    /// `static`, `abstract`, `private`/`protected`/`public`, `override`,
+    /// `async`, `declare`, in that order when present.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modifiers: Vec<String>,
+    /// Every signature of an overloaded function or method, in source order.
+    /// `signature` above is the implementation's when there is one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overloads: Vec<Overload>,
+    /// Constructors only: parameter properties (`private readonly db: Db`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameter_properties: Vec<String>,
+    /// Named types the signature references (`User`, `ns.Props`), as written.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_refs: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Overload {
+    pub signature: String,
+    /// True for the signature that carries the body.
+    pub implementation: bool,
+}
+
+/// Source position of a node: byte offsets (`end` exclusive) plus the 1-based
+/// line/column range. Columns count characters, not bytes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Location {
+    pub start: u32,
+    pub end: u32,
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
 }
 
 pub const CALLABLE_KINDS: &[&str] = &["function", "arrow_function", "method", "component"];
@@ -134,12 +245,12 @@ fn one_line(s: &str) -> String {
     }
 }
 
-fn is_pascal_case(name: &str) -> bool {
+pub fn is_pascal_case(name: &str) -> bool {
     name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
 }
 
 /// True when a binding's type annotation references `React.FC` / `FC`.
-fn annotation_is_fc(decl: &VariableDeclarator<'_>, source_text: &str) -> bool {
+pub fn annotation_is_fc(decl: &VariableDeclarator<'_>, source_text: &str) -> bool {
     decl.type_annotation.as_ref().is_some_and(|ann| {
         let span = ann.span();
         let text = &source_text[span.start as usize..span.end as usize];
@@ -149,9 +260,53 @@ fn annotation_is_fc(decl: &VariableDeclarator<'_>, source_text: &str) -> bool {
     })
 }
 
+/// Byte offsets of each line start, for span -> line/column conversion.
+struct LineIndex {
+    starts: Vec<u32>,
+}
+
+impl LineIndex {
+    fn new(text: &str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(
+            text.bytes()
+                .enumerate()
+                .filter(|(_, b)| *b == b'\n')
+                .map(|(i, _)| i as u32 + 1),
+        );
+        Self { starts }
+    }
+
+    /// 1-based (line, column) of a byte offset.
+    fn line_col(&self, text: &str, offset: u32) -> (u32, u32) {
+        let line = self.starts.partition_point(|s| *s <= offset) - 1;
+        let line_start = self.starts[line] as usize;
+        let col = text
+            .get(line_start..offset as usize)
+            .map_or(0, |s| s.chars().count());
+        (line as u32 + 1, col as u32 + 1)
+    }
+}
+
 struct SymbolContext<'s> {
     source_text: &'s str,
     is_tsx: bool,
+    lines: LineIndex,
+}
+
+impl SymbolContext<'_> {
+    fn locate(&self, span: Span) -> Location {
+        let (start_line, start_column) = self.lines.line_col(self.source_text, span.start);
+        let (end_line, end_column) = self.lines.line_col(self.source_text, span.end);
+        Location {
+            start: span.start,
+            end: span.end,
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+        }
+    }
 }
 
 fn component_or(ctx: &SymbolContext, name: &str, fallback: &str) -> String {
@@ -162,41 +317,64 @@ fn component_or(ctx: &SymbolContext, name: &str, fallback: &str) -> String {
     }
 }
 
+/// `outer_span` is the enclosing statement's span (so exported symbols'
+/// locations include `export`, like `get_implementation` slices).
 fn collect_decl_symbols(
     decl: &Declaration<'_>,
     exported: bool,
+    outer_span: Span,
     ctx: &SymbolContext,
     out: &mut Vec<SymbolInfo>,
 ) {
     match decl {
         Declaration::FunctionDeclaration(f) => {
             if let Some(id) = &f.id {
-                out.push(SymbolInfo {
+                let sym = SymbolInfo {
                     name: id.name.to_string(),
                     kind: component_or(ctx, &id.name, "function"),
                     exported,
                     signature: one_line(&stringify_item(&**f)),
-                });
+                    location: ctx.locate(outer_span),
+                    modifiers: function_modifiers(f),
+                    ..Default::default()
+                };
+                push_overloadable(out, sym, f.body.is_some());
             }
         }
         Declaration::ClassDeclaration(c) => {
             if let Some(name) = c.id.as_ref().map(|id| id.name.to_string()) {
+                let mut modifiers = Vec::new();
+                if c.r#abstract {
+                    modifiers.push("abstract".to_string());
+                }
+                if c.declare {
+                    modifiers.push("declare".to_string());
+                }
                 out.push(SymbolInfo {
                     name: name.clone(),
                     kind: "class".to_string(),
                     exported,
                     signature: one_line(&stringify_item(&**c)),
+                    location: ctx.locate(outer_span),
+                    modifiers,
+                    ..Default::default()
                 });
                 for el in &c.body.body {
-                    if let ClassElement::MethodDefinition(m) = el {
-                        if let Some(mn) = m.key.static_name() {
-                            out.push(SymbolInfo {
-                                name: format!("{}.{}", name, mn),
-                                kind: "method".to_string(),
-                                exported,
-                                signature: one_line(&stringify_item(&**m)),
-                            });
-                        }
+                    if let ClassElement::MethodDefinition(m) = el
+                        && let Some(mn) = member_name(&m.key)
+                    {
+                        let sym = SymbolInfo {
+                            name: format!("{}.{}", name, mn),
+                            kind: "method".to_string(),
+                            exported,
+                            signature: one_line(&stringify_item(&**m)),
+                            location: ctx.locate(m.span),
+                            method_kind: Some(method_kind(m.kind).to_string()),
+                            modifiers: method_modifiers(m),
+                            parameter_properties: parameter_properties(m, ctx),
+                            ..Default::default()
+                        };
+                        push_overloadable(out, sym, m.value.body.is_some());
                     }
                 }
             }
@@ -211,6 +389,9 @@ fn collect_decl_symbols(
                 let kind = match &d.init {
                     Some(Expression::ArrowFunctionExpression(_)) if is_component => "component",
                     Some(Expression::FunctionExpression(_)) if is_component => "component",
+                    Some(init) if is_component && components::unwrap_component(init).is_some() => {
+                        "component"
+                    }
                     Some(Expression::ArrowFunctionExpression(_)) => "arrow_function",
                     Some(Expression::FunctionExpression(_)) => "function",
                     _ => "variable",
@@ -220,11 +401,18 @@ fn collect_decl_symbols(
                 } else {
                     stringify_item(d)
                 };
+                let span = if v.declarations.len() == 1 {
+                    outer_span
+                } else {
+                    d.span
+                };
                 out.push(SymbolInfo {
                     name: name.to_string(),
                     kind: kind.to_string(),
                     exported,
                     signature: one_line(&format!("{} {}", v.kind.as_str(), decl_text)),
+                    location: ctx.locate(span),
+                    ..Default::default()
                 });
             }
         }
@@ -233,18 +421,24 @@ fn collect_decl_symbols(
             kind: "interface".to_string(),
             exported,
             signature: one_line(&stringify_item(&**i)),
+            location: ctx.locate(outer_span),
+            ..Default::default()
         }),
         Declaration::TSTypeAliasDeclaration(t) => out.push(SymbolInfo {
             name: t.id.name.to_string(),
             kind: "type".to_string(),
             exported,
             signature: one_line(&stringify_item(&**t)),
+            location: ctx.locate(outer_span),
+            ..Default::default()
         }),
         Declaration::TSEnumDeclaration(e) => out.push(SymbolInfo {
             name: e.id.name.to_string(),
             kind: "enum".to_string(),
             exported,
             signature: one_line(&stringify_item(&**e)),
+            location: ctx.locate(outer_span),
+            ..Default::default()
         }),
         _ => {}
     }
@@ -256,7 +450,7 @@ fn collect_symbols(program: &Program<'_>, ctx: &SymbolContext) -> Vec<SymbolInfo
         match stmt {
             Statement::ExportNamedDeclaration(e) => {
                 if let Some(d) = &e.declaration {
-                    collect_decl_symbols(d, true, ctx, &mut out);
+                    collect_decl_symbols(d, true, e.span, ctx, &mut out);
                 }
             }
             Statement::ExportDefaultDeclaration(e) => {
@@ -289,41 +483,308 @@ fn collect_symbols(program: &Program<'_>, ctx: &SymbolContext) -> Vec<SymbolInfo
                     kind,
                     exported: true,
                     signature: sig,
+                    location: ctx.locate(e.span),
+                    ..Default::default()
                 });
             }
             _ => {
                 if let Some(d) = stmt.as_declaration() {
-                    collect_decl_symbols(d, false, ctx, &mut out);
+                    collect_decl_symbols(d, false, stmt.span(), ctx, &mut out);
+                }
+            }
+        }
+    }
+    // A lone bodiless signature (abstract method, `declare function`) is not
+    // an overload set.
+    for sym in &mut out {
+        if sym.overloads.len() == 1 {
+            sym.overloads.clear();
+        }
+    }
+    out
+}
+
+fn export_entry(name: String, local: Option<String>, type_only: bool) -> ExportEntry {
+    ExportEntry {
+        name,
+        local,
+        source: None,
+        imported: None,
+        type_only,
+    }
+}
+
+fn reexport_entry(name: String, source: &str, imported: String, type_only: bool) -> ExportEntry {
+    ExportEntry {
+        name,
+        local: None,
+        source: Some(source.to_string()),
+        imported: Some(imported),
+        type_only,
+    }
+}
+
+/// The export table, each entry paired with the span of its statement.
+fn collect_exports(program: &Program<'_>) -> Vec<(ExportEntry, Span)> {
+    let mut out = Vec::new();
+    for stmt in &program.body {
+        let span = stmt.span();
+        match stmt {
+            Statement::ExportNamedDeclaration(e) => {
+                if let Some(d) = &e.declaration {
+                    let mut names = Vec::new();
+                    declaration_names(d, &mut names);
+                    let type_only = matches!(
+                        d,
+                        Declaration::TSInterfaceDeclaration(_) | Declaration::TSTypeAliasDeclaration(_)
+                    );
+                    for n in names.into_iter().filter(|n| !n.contains('.')) {
+                        out.push((export_entry(n.clone(), Some(n), type_only), span));
+                    }
                 }
+                for spec in &e.specifiers {
+                    let type_only = e.export_kind.is_type() || spec.export_kind.is_type();
+                    let exported = spec.exported.name().to_string();
+                    let local = spec.local.name().to_string();
+                    let entry = match &e.source {
+                        Some(src) => reexport_entry(exported, &src.value, local, type_only),
+                        None => export_entry(exported, Some(local), type_only),
+                    };
+                    out.push((entry, span));
+                }
+            }
+            Statement::ExportDefaultDeclaration(e) => {
+                let local = match &e.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(f) => {
+                        f.id.as_ref().map(|id| id.name.to_string())
+                    }
+                    ExportDefaultDeclarationKind::ClassDeclaration(c) => {
+                        class_name(c).map(str::to_string)
+                    }
+                    ExportDefaultDeclarationKind::Identifier(id) => Some(id.name.to_string()),
+                    _ => None,
+                };
+                let local = local.unwrap_or_else(|| "default".to_string());
+                out.push((export_entry("default".to_string(), Some(local), false), span));
             }
+            Statement::ExportAllDeclaration(e) => {
+                let name = e
+                    .exported
+                    .as_ref()
+                    .map(|n| n.name().to_string())
+                    .unwrap_or_else(|| "*".to_string());
+                let entry =
+                    reexport_entry(name, &e.source.value, "*".to_string(), e.export_kind.is_type());
+                out.push((entry, span));
+            }
+            _ => {}
         }
     }
     out
 }
 
-fn import_record(decl: &ImportDeclaration<'_>) -> ImportRecord {
-    let names = decl
+/// One-line `export { a as b } from "./x"` text for a forwarded name.
+fn reexport_signature(entry: &ExportEntry, source: &str) -> String {
+    let imported = entry
+        .imported
+        .as_deref()
+        .or(entry.local.as_deref())
+        .unwrap_or_default();
+    let type_kw = if entry.type_only { "type " } else { "" };
+    match imported {
+        "*" => format!("export {}* as {} from \"{}\"", type_kw, entry.name, source),
+        i if i == entry.name => format!("export {}{{ {} }} from \"{}\"", type_kw, i, source),
+        i => format!(
+            "export {}{{ {} as {} }} from \"{}\"",
+            type_kw, i, entry.name, source
+        ),
+    }
+}
+
+/// Reconcile the symbol table with the export table: locals exported through
+/// `export { a as b }` / `export default a` are marked exported (with their
+/// members), and names forwarded from other modules — `export { X } from`,
+/// `export * as ns from`, or an import re-exported by name — become
+/// `reexport` symbols so barrel files are not empty.
+fn apply_exports(ir: &mut FileSkeleton, exports: &[(ExportEntry, Span)], ctx: &SymbolContext) {
+    let mut reexports = Vec::new();
+    for (entry, span) in exports {
+        // An import exported onwards forwards the name it was imported as
+        // (`import { helper as h }; export { h }` is `export { helper as h }`).
+        let mut forwarded = None;
+        let source = match (&entry.local, &entry.source) {
+            (Some(local), _) => {
+                let prefix = format!("{}.", local);
+                let mut declared = false;
+                for sym in ir
+                    .symbols
+                    .iter_mut()
+                    .filter(|s| s.name == *local || s.name.starts_with(&prefix))
+                {
+                    sym.exported = true;
+                    declared = true;
+                }
+                if declared {
+                    continue;
+                }
+                let import = ir.import_records.iter().find_map(|r| {
+                    r.bindings
+                        .iter()
+                        .find(|b| b.local == *local)
+                        .map(|b| (r, b))
+                });
+                let Some((rec, binding)) = import else {
+                    continue;
+                };
+                forwarded = Some(reexport_entry(
+                    entry.name.clone(),
+                    &rec.source,
+                    binding.imported.clone(),
+                    entry.type_only || binding.type_only,
+                ));
+                rec.source.clone()
+            }
+            (None, Some(_)) if entry.name == "*" => continue,
+            (None, Some(source)) => source.clone(),
+            (None, None) => continue,
+        };
+        reexports.push(SymbolInfo {
+            name: entry.name.clone(),
+            kind: "reexport".to_string(),
+            exported: true,
+            signature: reexport_signature(forwarded.as_ref().unwrap_or(entry), &source),
+            location: ctx.locate(*span),
+            ..Default::default()
+        });
+    }
+    ir.symbols.extend(reexports);
+}
+
+fn method_kind(kind: MethodDefinitionKind) -> &'static str {
+    match kind {
+        MethodDefinitionKind::Constructor => "constructor",
+        MethodDefinitionKind::Method => "method",
+        MethodDefinitionKind::Get => "getter",
+        MethodDefinitionKind::Set => "setter",
+    }
+}
+
+fn function_modifiers(f: &Function<'_>) -> Vec<String> {
+    let mut out = Vec::new();
+    if f.r#async {
+        out.push("async".to_string());
+    }
+    if f.declare {
+        out.push("declare".to_string());
+    }
+    out
+}
+
+fn method_modifiers(m: &MethodDefinition<'_>) -> Vec<String> {
+    let mut out = Vec::new();
+    if m.r#static {
+        out.push("static".to_string());
+    }
+    if m.r#type == MethodDefinitionType::TSAbstractMethodDefinition {
+        out.push("abstract".to_string());
+    }
+    if let Some(a) = m.accessibility {
+        out.push(a.as_str().to_string());
+    }
+    if m.r#override {
+        out.push("override".to_string());
+    }
+    if m.value.r#async {
+        out.push("async".to_string());
+    }
+    out
+}
+
+/// Constructor parameters declared with an accessibility, `readonly` or
+/// `override` modifier, as written (`private readonly db: Db`).
+fn parameter_properties(m: &MethodDefinition<'_>, ctx: &SymbolContext) -> Vec<String> {
+    if m.kind != MethodDefinitionKind::Constructor {
+        return Vec::new();
+    }
+    m.value
+        .params
+        .items
+        .iter()
+        .filter(|p| p.accessibility.is_some() || p.readonly || p.r#override)
+        .map(|p| one_line(&ctx.source_text[p.span.start as usize..p.span.end as usize]))
+        .collect()
+}
+
+/// Push a function/method symbol, folding it into the previous symbol when
+/// both belong to one TypeScript overload set: bodiless signatures followed
+/// by at most one implementation, all with the same name, kind and modifiers.
+fn push_overloadable(out: &mut Vec<SymbolInfo>, mut sym: SymbolInfo, implementation: bool) {
+    let entry = Overload {
+        signature: sym.signature.clone(),
+        implementation,
+    };
+    if let Some(prev) = out.last_mut()
+        && prev.name == sym.name
+        && prev.kind == sym.kind
+        && prev.method_kind == sym.method_kind
+        && prev.modifiers.contains(&"static".to_string())
+            == sym.modifiers.contains(&"static".to_string())
+        && !prev.overloads.is_empty()
+        && !prev.overloads.iter().any(|o| o.implementation)
+    {
+        prev.overloads.push(entry);
+        prev.location.end = sym.location.end;
+        prev.location.end_line = sym.location.end_line;
+        prev.location.end_column = sym.location.end_column;
+        if implementation {
+            prev.signature = sym.signature;
+            prev.modifiers = sym.modifiers;
+            prev.parameter_properties = sym.parameter_properties;
+        }
+        return;
+    }
+    if !implementation {
+        sym.overloads.push(entry);
+    }
+    out.push(sym);
+}
+
+fn import_record(decl: &ImportDeclaration<'_>, ctx: &SymbolContext) -> ImportRecord {
+    let type_only = decl.import_kind.is_type();
+    let bindings: Vec<ImportBinding> = decl
         .specifiers
         .as_ref()
         .map(|specs| {
             specs
                 .iter()
                 .map(|s| match s {
-                    ImportDeclarationSpecifier::ImportSpecifier(s) => s.local.name.to_string(),
-                    ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
-                        s.local.name.to_string()
-                    }
-                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
-                        s.local.name.to_string()
-                    }
+                    ImportDeclarationSpecifier::ImportSpecifier(s) => ImportBinding {
+                        imported: s.imported.name().to_string(),
+                        local: s.local.name.to_string(),
+                        type_only: type_only || s.import_kind.is_type(),
+                    },
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => ImportBinding {
+                        imported: "default".to_string(),
+                        local: s.local.name.to_string(),
+                        type_only,
+                    },
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => ImportBinding {
+                        imported: "*".to_string(),
+                        local: s.local.name.to_string(),
+                        type_only,
+                    },
                 })
                 .collect()
         })
         .unwrap_or_default();
     ImportRecord {
         source: decl.source.value.to_string(),
-        names,
-        type_only: decl.import_kind.is_type(),
+        names: bindings.iter().map(|b| b.local.clone()).collect(),
+        type_only,
+        location: ctx.locate(decl.span),
+        bindings,
+        dynamic: false,
+        resolved: None,
     }
 }
 
@@ -396,7 +857,7 @@ fn extract_ir(program: &Program<'_>, ctx: &SymbolContext) -> FileSkeleton {
         match stmt {
             Statement::ImportDeclaration(decl) => {
                 ir.imports.push(stringify_item(&**decl));
-                ir.import_records.push(import_record(decl));
+                ir.import_records.push(import_record(decl, ctx));
             }
             Statement::ExportNamedDeclaration(decl) => {
                 let text = match &decl.declaration {
@@ -405,14 +866,24 @@ fn extract_ir(program: &Program<'_>, ctx: &SymbolContext) -> FileSkeleton {
                 };
                 ir.exports.push(doc(text));
                 if let Some(src) = &decl.source {
+                    let type_only = decl.export_kind.is_type();
+                    let bindings: Vec<ImportBinding> = decl
+                        .specifiers
+                        .iter()
+                        .map(|s| ImportBinding {
+                            imported: s.local.name().to_string(),
+                            local: s.exported.name().to_string(),
+                            type_only: type_only || s.export_kind.is_type(),
+                        })
+                        .collect();
                     ir.import_records.push(ImportRecord {
                         source: src.value.to_string(),
-                        names: decl
-                            .specifiers
-                            .iter()
-                            .map(|s| s.exported.name().to_string())
-                            .collect(),
-                        type_only: decl.export_kind.is_type(),
+                        names: bindings.iter().map(|b| b.local.clone()).collect(),
+                        type_only,
+                        location: ctx.locate(decl.span),
+                        bindings,
+                        dynamic: false,
+                        resolved: None,
                     });
                 }
             }
@@ -421,10 +892,24 @@ fn extract_ir(program: &Program<'_>, ctx: &SymbolContext) -> FileSkeleton {
             }
             Statement::ExportAllDeclaration(decl) => {
                 ir.exports.push(stringify_item(&**decl));
+                let type_only = decl.export_kind.is_type();
+                let local = decl
+                    .exported
+                    .as_ref()
+                    .map(|n| n.name().to_string())
+                    .unwrap_or_else(|| "*".to_string());
                 ir.import_records.push(ImportRecord {
                     source: decl.source.value.to_string(),
                     names: vec!["*".to_string()],
-                    type_only: decl.export_kind.is_type(),
+                    type_only,
+                    location: ctx.locate(decl.span),
+                    bindings: vec![ImportBinding {
+                        imported: "*".to_string(),
+                        local,
+                        type_only,
+                    }],
+                    dynamic: false,
+                    resolved: None,
                 });
             }
             Statement::TSImportEqualsDeclaration(decl) => ir.imports.push(stringify_item(&**decl)),
@@ -451,21 +936,97 @@ fn extract_ir(program: &Program<'_>, ctx: &SymbolContext) -> FileSkeleton {
         }
     }
     ir.symbols = collect_symbols(program, ctx);
+    let exports = collect_exports(program);
+    apply_exports(&mut ir, &exports, ctx);
+    ir.export_table = exports.into_iter().map(|(e, _)| e).collect();
     ir
 }
 
+/// String-literal `import()` expressions anywhere in a program, collected
+/// before function bodies are stripped.
+#[derive(Default)]
+struct DynamicImports {
+    found: Vec<(String, Span)>,
+}
+
+impl<'a> Visit<'a> for DynamicImports {
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        match &it.source {
+            Expression::StringLiteral(s) => self.found.push((s.value.to_string(), it.span)),
+            Expression::TemplateLiteral(t) if t.expressions.is_empty() => {
+                if let Some(q) = t.quasis.first() {
+                    self.found.push((q.value.raw.to_string(), it.span));
+                }
+            }
+            _ => {}
+        }
+        walk::walk_import_expression(self, it);
+    }
+}
+
 pub fn skeletonize_source(source_text: &str, path: &Path) -> Result<FileSkeleton> {
     let allocator = Allocator::default();
     let mut program = parse_source(&allocator, source_text, path)?;
 
-    let mut skeletonizer = Skeletonizer;
-    skeletonizer.visit_program(&mut program);
-
     let ctx = SymbolContext {
         source_text,
         is_tsx: path.extension().and_then(|e| e.to_str()) == Some("tsx"),
+        lines: LineIndex::new(source_text),
+    };
+    let mut dynamic = DynamicImports::default();
+    dynamic.visit_program(&program);
+    let raw_calls = calls::collect(&program);
+    let type_refs = types::signature_type_refs(&program);
+    let components = if ctx.is_tsx {
+        components::extract(&program, source_text)
+    } else {
+        Vec::new()
     };
-    Ok(extract_ir(&program, &ctx))
+    let routes = routes::collect(&program);
+
+    let mut skeletonizer = Skeletonizer;
+    skeletonizer.visit_program(&mut program);
+
+    let mut ir = extract_ir(&program, &ctx);
+    ir.import_records
+        .extend(dynamic.found.into_iter().map(|(source, span)| ImportRecord {
+            source,
+            names: vec!["*".to_string()],
+            type_only: false,
+            location: ctx.locate(span),
+            bindings: vec![ImportBinding {
+                imported: "*".to_string(),
+                local: "*".to_string(),
+                type_only: false,
+            }],
+            dynamic: true,
+            resolved: None,
+        }));
+    (ir.call_graph, ir.imported_calls) = raw_calls.link(&ir.import_records, |offset| {
+        ctx.lines.line_col(source_text, offset).0
+    });
+    for sym in &mut ir.symbols {
+        if let Some(refs) = type_refs.get(&sym.name) {
+            sym.type_refs = refs.iter().cloned().collect();
+        }
+    }
+    ir.components = components
+        .into_iter()
+        .map(|mut c| {
+            if let Some(sym) = ir.symbols.iter().find(|s| s.name == c.name) {
+                c.location = sym.location;
+            }
+            c
+        })
+        .collect();
+    ir.routes = routes
+        .into_iter()
+        .map(|(mut route, span)| {
+            route.location = ctx.locate(span);
+            route
+        })
+        .collect();
+    Ok(ir)
 }
 
 pub fn skeletonize_file(path: &Path) -> Result<FileSkeleton> {
@@ -474,118 +1035,296 @@ pub fn skeletonize_file(path: &Path) -> Result<FileSkeleton> {
 }
 
 pub enum ImplLookup {
-    /// Original source text of the requested node, sliced by span.
+    /// Original source text of the requested node, sliced by span. Overloads
+    /// and get/set pairs sharing a name are returned together.
     Found(String),
-    /// Node not found; carries the available top-level symbol names.
+    /// First path segment not found; carries the available top-level symbol
+    /// names.
     NotFound(Vec<String>),
+    /// A later segment not found; carries the resolved parent path and the
+    /// names addressable directly under it.
+    ChildNotFound { parent: String, children: Vec<String> },
 }
 
 pub fn get_implementation(path: &Path, target_node: &str) -> Result<ImplLookup> {
-    let allocator = Allocator::default();
     let source_text = std::fs::read_to_string(path).context("failed to load file")?;
-    let program = parse_source(&allocator, &source_text, path)?;
+    get_implementation_source(&source_text, path, target_node)
+}
 
-    match find_target_span(&program, target_node) {
-        Some(span) => Ok(ImplLookup::Found(
-            source_text[span.start as usize..span.end as usize].to_string(),
-        )),
-        None => Ok(ImplLookup::NotFound(top_level_names(&program))),
-    }
+/// `get_implementation` over in-memory source (e.g. a file at a git
+/// revision); `path` only selects the dialect.
+pub fn get_implementation_source(source_text: &str, path: &Path, target_node: &str) -> Result<ImplLookup> {
+    let allocator = Allocator::default();
+    let program = parse_source(&allocator, source_text, path)?;
+
+    Ok(match resolve_target(&program, target_node) {
+        Ok(spans) => ImplLookup::Found(
+            spans
+                .iter()
+                .map(|s| &source_text[s.start as usize..s.end as usize])
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+        Err(None) => ImplLookup::NotFound(top_level_names(&program)),
+        Err(Some((parent, children))) => ImplLookup::ChildNotFound { parent, children },
+    })
 }
 
 fn class_name<'a>(class: &Class<'a>) -> Option<&'a str> {
     class.id.as_ref().map(|id| id.name.as_str())
 }
 
-fn method_span(class: &Class<'_>, method: &str) -> Option<Span> {
-    class.body.body.iter().find_map(|el| match el {
-        ClassElement::MethodDefinition(m)
-            if m.key.static_name().is_some_and(|n| n == method) =>
-        {
-            Some(m.span)
-        }
+/// Something a dotted target path can descend into.
+#[derive(Clone, Copy)]
+enum Scope<'b, 'a> {
+    Statements(&'b [Statement<'a>]),
+    Module(&'b TSModuleDeclaration<'a>),
+    Class(&'b Class<'a>),
+    Object(&'b ObjectExpression<'a>),
+    /// Interface bodies and object type literals.
+    Signatures(&'b [TSSignature<'a>]),
+    Enum(&'b TSEnumBody<'a>),
+}
+
+/// One addressable child: its name, the span `get_implementation` returns,
+/// and what can be navigated into from it.
+struct PathNode<'b, 'a> {
+    name: String,
+    span: Span,
+    scope: Option<Scope<'b, 'a>>,
+}
+
+pub fn member_name(key: &PropertyKey<'_>) -> Option<String> {
+    match key {
+        PropertyKey::PrivateIdentifier(p) => Some(format!("#{}", p.name)),
+        _ => key.static_name().map(|n| n.into_owned()),
+    }
+}
+
+fn module_name(m: &TSModuleDeclaration<'_>) -> String {
+    match &m.id {
+        TSModuleDeclarationName::Identifier(id) => id.name.to_string(),
+        TSModuleDeclarationName::StringLiteral(s) => s.value.to_string(),
+    }
+}
+
+/// Object literals and class expressions (through parens, `as const`,
+/// `satisfies`) can be navigated into.
+fn expression_scope<'b, 'a>(expr: &'b Expression<'a>) -> Option<Scope<'b, 'a>> {
+    match expr.get_inner_expression() {
+        Expression::ObjectExpression(o) => Some(Scope::Object(o)),
+        Expression::ClassExpression(c) => Some(Scope::Class(c)),
         _ => None,
-    })
+    }
 }
 
-/// Match `target` against a top-level declaration. `outer_span` is the span of
-/// the enclosing statement (so `export function foo` slices include `export`).
-fn match_declaration(decl: &Declaration<'_>, target: &str, outer_span: Span) -> Option<Span> {
-    let (class_part, method_part) = match target.split_once('.') {
-        Some((c, m)) => (Some(c), Some(m)),
-        None => (None, None),
+/// `outer_span` is the span of the enclosing statement (so `export function
+/// foo` slices include `export`).
+fn declaration_children<'b, 'a>(
+    decl: &'b Declaration<'a>,
+    outer_span: Span,
+    out: &mut Vec<PathNode<'b, 'a>>,
+) {
+    let mut push = |name: &str, span: Span, scope: Option<Scope<'b, 'a>>| {
+        out.push(PathNode {
+            name: name.to_string(),
+            span,
+            scope,
+        })
     };
-
     match decl {
         Declaration::FunctionDeclaration(f) => {
-            (f.id.as_ref().is_some_and(|id| id.name == target)).then_some(outer_span)
+            if let Some(id) = &f.id {
+                push(&id.name, outer_span, None);
+            }
         }
         Declaration::ClassDeclaration(c) => {
-            if class_name(c) == Some(target) {
-                return Some(outer_span);
-            }
-            if let (Some(cls), Some(m)) = (class_part, method_part) {
-                if class_name(c) == Some(cls) {
-                    return method_span(c, m);
-                }
+            if let Some(name) = class_name(c) {
+                push(name, outer_span, Some(Scope::Class(c)));
             }
-            None
         }
-        Declaration::VariableDeclaration(v) => v.declarations.iter().find_map(|d| {
-            if d.id.get_identifier_name().is_some_and(|n| n == target) {
-                if v.declarations.len() == 1 {
-                    Some(outer_span)
-                } else {
-                    Some(d.span)
+        Declaration::VariableDeclaration(v) => {
+            for d in &v.declarations {
+                if let Some(name) = d.id.get_identifier_name() {
+                    let span = if v.declarations.len() == 1 {
+                        outer_span
+                    } else {
+                        d.span
+                    };
+                    push(&name, span, d.init.as_ref().and_then(expression_scope));
                 }
-            } else {
-                None
             }
-        }),
+        }
         Declaration::TSInterfaceDeclaration(i) => {
-            (i.id.name == target).then_some(outer_span)
+            push(&i.id.name, outer_span, Some(Scope::Signatures(&i.body.body)))
         }
-        Declaration::TSTypeAliasDeclaration(t) => (t.id.name == target).then_some(outer_span),
-        Declaration::TSEnumDeclaration(e) => (e.id.name == target).then_some(outer_span),
-        _ => None,
+        Declaration::TSTypeAliasDeclaration(t) => {
+            let scope = match &t.type_annotation {
+                TSType::TSTypeLiteral(lit) => Some(Scope::Signatures(&lit.members)),
+                _ => None,
+            };
+            push(&t.id.name, outer_span, scope)
+        }
+        Declaration::TSEnumDeclaration(e) => push(&e.id.name, outer_span, Some(Scope::Enum(&e.body))),
+        Declaration::TSModuleDeclaration(m) => {
+            push(&module_name(m), outer_span, Some(Scope::Module(m)))
+        }
+        _ => {}
     }
 }
 
-fn find_target_span(program: &Program<'_>, target: &str) -> Option<Span> {
-    for stmt in &program.body {
+fn statement_children<'b, 'a>(stmts: &'b [Statement<'a>], out: &mut Vec<PathNode<'b, 'a>>) {
+    for stmt in stmts {
         let outer_span = stmt.span();
-        let found = match stmt {
-            Statement::FunctionDeclaration(_)
-            | Statement::ClassDeclaration(_)
-            | Statement::VariableDeclaration(_)
-            | Statement::TSInterfaceDeclaration(_)
-            | Statement::TSTypeAliasDeclaration(_)
-            | Statement::TSEnumDeclaration(_) => stmt
-                .as_declaration()
-                .and_then(|d| match_declaration(d, target, outer_span)),
-            Statement::ExportNamedDeclaration(e) => e
-                .declaration
-                .as_ref()
-                .and_then(|d| match_declaration(d, target, outer_span)),
+        match stmt {
+            Statement::ExportNamedDeclaration(e) => {
+                if let Some(d) = &e.declaration {
+                    declaration_children(d, outer_span, out);
+                }
+            }
             Statement::ExportDefaultDeclaration(e) => {
-                let named = match &e.declaration {
+                let (name, scope) = match &e.declaration {
                     ExportDefaultDeclarationKind::FunctionDeclaration(f) => {
-                        f.id.as_ref().is_some_and(|id| id.name == target)
+                        (f.id.as_ref().map(|id| id.name.as_str()), None)
                     }
                     ExportDefaultDeclarationKind::ClassDeclaration(c) => {
-                        class_name(c) == Some(target)
+                        (class_name(c), Some(Scope::Class(c)))
                     }
-                    _ => false,
+                    kind => (None, kind.as_expression().and_then(expression_scope)),
                 };
-                (target == "default" || named).then_some(outer_span)
+                for n in std::iter::once("default").chain(name) {
+                    out.push(PathNode {
+                        name: n.to_string(),
+                        span: outer_span,
+                        scope,
+                    });
+                }
             }
-            _ => None,
-        };
-        if found.is_some() {
-            return found;
+            _ => {
+                if let Some(d) = stmt.as_declaration() {
+                    declaration_children(d, outer_span, out);
+                }
+            }
+        }
+    }
+}
+
+fn children<'b, 'a>(scope: Scope<'b, 'a>) -> Vec<PathNode<'b, 'a>> {
+    let mut out = Vec::new();
+    let mut push = |name: Option<String>, span: Span, scope: Option<Scope<'b, 'a>>| {
+        if let Some(name) = name {
+            out.push(PathNode { name, span, scope });
+        }
+    };
+    match scope {
+        Scope::Statements(stmts) => statement_children(stmts, &mut out),
+        Scope::Module(m) => match &m.body {
+            Some(TSModuleDeclarationBody::TSModuleBlock(block)) => {
+                statement_children(&block.body, &mut out)
+            }
+            // `namespace A.B {}` nests B's declaration directly under A.
+            Some(TSModuleDeclarationBody::TSModuleDeclaration(inner)) => {
+                push(Some(module_name(inner)), inner.span, Some(Scope::Module(inner)))
+            }
+            None => {}
+        },
+        Scope::Class(c) => {
+            for el in &c.body.body {
+                match el {
+                    ClassElement::MethodDefinition(m) => push(member_name(&m.key), m.span, None),
+                    ClassElement::PropertyDefinition(p) => push(
+                        member_name(&p.key),
+                        p.span,
+                        p.value.as_ref().and_then(expression_scope),
+                    ),
+                    ClassElement::AccessorProperty(a) => push(member_name(&a.key), a.span, None),
+                    ClassElement::StaticBlock(b) => push(Some("static".to_string()), b.span, None),
+                    ClassElement::TSIndexSignature(_) => {}
+                }
+            }
+        }
+        Scope::Object(o) => {
+            for prop in &o.properties {
+                if let ObjectPropertyKind::ObjectProperty(p) = prop {
+                    push(member_name(&p.key), p.span, expression_scope(&p.value));
+                }
+            }
+        }
+        Scope::Signatures(sigs) => {
+            for sig in sigs {
+                match sig {
+                    TSSignature::TSPropertySignature(p) => {
+                        let scope = match p.type_annotation.as_ref().map(|a| &a.type_annotation) {
+                            Some(TSType::TSTypeLiteral(lit)) => Some(Scope::Signatures(&lit.members)),
+                            _ => None,
+                        };
+                        push(member_name(&p.key), p.span, scope)
+                    }
+                    TSSignature::TSMethodSignature(m) => push(member_name(&m.key), m.span, None),
+                    _ => {}
+                }
+            }
+        }
+        Scope::Enum(body) => {
+            for m in &body.members {
+                push(Some(m.id.static_name().to_string()), m.span, None);
+            }
+        }
+    }
+    out
+}
+
+/// Splits a target path on `.`; a quoted leading segment (`"a.b".x` for
+/// `declare module "a.b"`) is kept whole, without its quotes.
+fn target_segments(target: &str) -> Vec<&str> {
+    if let Some(quote) = target.chars().next().filter(|c| matches!(c, '"' | '\''))
+        && let Some(end) = target[1..].find(quote)
+    {
+        let rest = &target[end + 2..];
+        let mut segments = vec![&target[1..end + 1]];
+        if let Some(rest) = rest.strip_prefix('.') {
+            segments.extend(rest.split('.'));
+        } else if !rest.is_empty() {
+            segments.push(rest);
         }
+        return segments;
     }
-    None
+    target.split('.').collect()
+}
+
+/// Resolve a dotted target path (`Utils.format`, `api.fetchUser`,
+/// `Shape.Kind.Circle`) to the spans of every node it names. Merged
+/// declarations (`namespace A` twice, class + namespace) are searched
+/// together. On failure returns `None` when the first segment is unknown,
+/// or the resolved parent path plus its available children.
+fn resolve_target(
+    program: &Program<'_>,
+    target: &str,
+) -> Result<Vec<Span>, Option<(String, Vec<String>)>> {
+    let segments = target_segments(target);
+    let mut scopes = vec![Scope::Statements(&program.body)];
+    for (i, seg) in segments.iter().enumerate() {
+        let all: Vec<PathNode> = scopes.iter().flat_map(|s| children(*s)).collect();
+        let hits: Vec<&PathNode> = all.iter().filter(|n| n.name == *seg).collect();
+        if hits.is_empty() {
+            if i == 0 {
+                return Err(None);
+            }
+            let mut names: Vec<String> = all.iter().map(|n| n.name.clone()).collect();
+            names.dedup();
+            return Err(Some((segments[..i].join("."), names)));
+        }
+        if i + 1 == segments.len() {
+            let mut spans: Vec<Span> = hits.iter().map(|n| n.span).collect();
+            spans.dedup();
+            return Ok(spans);
+        }
+        scopes = hits.iter().filter_map(|n| n.scope).collect();
+        if scopes.is_empty() {
+            return Err(Some((segments[..=i].join("."), Vec::new())));
+        }
+    }
+    Err(None)
 }
 
 fn declaration_names(decl: &Declaration<'_>, out: &mut Vec<String>) {
@@ -599,10 +1338,10 @@ fn declaration_names(decl: &Declaration<'_>, out: &mut Vec<String>) {
             if let Some(name) = class_name(c) {
                 out.push(name.to_string());
                 for el in &c.body.body {
-                    if let ClassElement::MethodDefinition(m) = el {
-                        if let Some(mn) = m.key.static_name() {
-                            out.push(format!("{}.{}", name, mn));
-                        }
+                    if let ClassElement::MethodDefinition(m) = el
+                        && let Some(mn) = m.key.static_name()
+                    {
+                        out.push(format!("{}.{}", name, mn));
                     }
                 }
             }
@@ -617,6 +1356,7 @@ fn declaration_names(decl: &Declaration<'_>, out: &mut Vec<String>) {
         Declaration::TSInterfaceDeclaration(i) => out.push(i.id.name.to_string()),
         Declaration::TSTypeAliasDeclaration(t) => out.push(t.id.name.to_string()),
         Declaration::TSEnumDeclaration(e) => out.push(e.id.name.to_string()),
+        Declaration::TSModuleDeclaration(m) => out.push(module_name(m)),
         _ => {}
     }
 }
@@ -643,6 +1383,110 @@ pub fn top_level_names(program: &Program<'_>) -> Vec<String> {
     names
 }
 
+/// Collects references to imported bindings, each target a `(local,
+/// member)` pair: bare `local` identifiers (values, types, JSX tags), or
+/// `local.member` accesses when `member` is set (namespace imports). Spans
+/// are tagged with the index of the target they match. Purely syntactic;
+/// shadowing is not tracked.
+struct UsageFinder<'q> {
+    targets: &'q [(&'q str, Option<&'q str>)],
+    spans: Vec<(usize, Span)>,
+}
+
+impl UsageFinder<'_> {
+    fn record(&mut self, local: &str, member: Option<&str>, span: Span) {
+        for (i, target) in self.targets.iter().enumerate() {
+            if target.0 == local && target.1 == member {
+                self.spans.push((i, span));
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for UsageFinder<'_> {
+    // The import statement itself is not a usage, nor is `export { x }`.
+    fn visit_import_declaration(&mut self, _it: &ImportDeclaration<'a>) {}
+
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        if let Some(decl) = &it.declaration {
+            self.visit_declaration(decl);
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, it: &IdentifierReference<'a>) {
+        self.record(&it.name, None, it.span);
+    }
+
+    fn visit_static_member_expression(&mut self, it: &StaticMemberExpression<'a>) {
+        if let Expression::Identifier(obj) = &it.object {
+            self.record(&obj.name, Some(&it.property.name), it.span);
+        }
+        walk::walk_static_member_expression(self, it);
+    }
+
+    fn visit_ts_qualified_name(&mut self, it: &TSQualifiedName<'a>) {
+        if let TSTypeName::IdentifierReference(left) = &it.left {
+            self.record(&left.name, Some(&it.right.name), it.span);
+        }
+        walk::walk_ts_qualified_name(self, it);
+    }
+
+    fn visit_jsx_member_expression(&mut self, it: &JSXMemberExpression<'a>) {
+        if let JSXMemberExpressionObject::IdentifierReference(obj) = &it.object {
+            self.record(&obj.name, Some(&it.property.name), it.span);
+        }
+        walk::walk_jsx_member_expression(self, it);
+    }
+}
+
+/// Locations where each of `targets` is used in a file, in target order,
+/// from a single parse; see `UsageFinder`.
+pub fn find_usages(path: &Path, targets: &[(&str, Option<&str>)]) -> Result<Vec<Vec<Location>>> {
+    let allocator = Allocator::default();
+    let source_text = std::fs::read_to_string(path).context("failed to load file")?;
+    let program = parse_source(&allocator, &source_text, path)?;
+    let mut finder = UsageFinder {
+        targets,
+        spans: Vec::new(),
+    };
+    finder.visit_program(&program);
+    let ctx = SymbolContext {
+        source_text: &source_text,
+        is_tsx: false,
+        lines: LineIndex::new(&source_text),
+    };
+    let mut out = vec![Vec::new(); targets.len()];
+    for (i, span) in finder.spans {
+        out[i].push(ctx.locate(span));
+    }
+    Ok(out)
+}
+
+/// Parser diagnostics for `source_text`, each at its first labelled span
+/// (the start of the file when it has none).
+pub fn syntax_errors(source_text: &str, path: &Path) -> Vec<(String, Location)> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(path).unwrap_or_default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let ctx = SymbolContext {
+        source_text,
+        is_tsx: false,
+        lines: LineIndex::new(source_text),
+    };
+    ret.errors
+        .iter()
+        .map(|error| {
+            let span = error
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.first())
+                .map(|l| Span::new(l.offset() as u32, (l.offset() + l.len()) as u32))
+                .unwrap_or_default();
+            (error.message.to_string(), ctx.locate(span))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -699,6 +1543,127 @@ const helper = () => 42;
         assert_eq!(ir.import_records[3].names, vec!["*"]);
     }
 
+    #[test]
+    fn literal_dynamic_imports_become_namespace_records() {
+        let src = "export async function load(name: string) {\n  const m = await import('./heavy');\n  const t = await import(`./tpl`);\n  return import(`./pages/${name}`);\n}\n";
+        let ir = skel(src, "x.ts");
+        let dynamic: Vec<(&str, u32)> = ir
+            .import_records
+            .iter()
+            .filter(|r| r.dynamic)
+            .map(|r| (r.source.as_str(), r.location.start_line))
+            .collect();
+        assert_eq!(dynamic, vec![("./heavy", 2), ("./tpl", 3)]);
+        assert_eq!(ir.import_records[0].bindings[0].imported, "*");
+        assert!(!ir.exports[0].contains("heavy"), "bodies are still stripped");
+    }
+
+    #[test]
+    fn symbols_and_imports_carry_source_locations() {
+        let src = "import { a } from './a';\n\nexport function hi(): string {\n  return 'é' + a;\n}\nclass C {\n  m() {}\n}\n";
+        let ir = skel(src, "loc.ts");
+        let hi = ir.symbols.iter().find(|s| s.name == "hi").unwrap();
+        assert_eq!(
+            &src[hi.location.start as usize..hi.location.end as usize],
+            "export function hi(): string {\n  return 'é' + a;\n}"
+        );
+        assert_eq!((hi.location.start_line, hi.location.start_column), (3, 1));
+        assert_eq!((hi.location.end_line, hi.location.end_column), (5, 2));
+        let m = ir.symbols.iter().find(|s| s.name == "C.m").unwrap();
+        assert_eq!((m.location.start_line, m.location.start_column), (7, 3));
+        let rec = &ir.import_records[0].location;
+        assert_eq!((rec.start_line, rec.start_column, rec.end_line), (1, 1, 1));
+    }
+
+    #[test]
+    fn overloads_accessors_and_constructors_are_distinguished() {
+        let src = r#"export function parse(x: string): number;
+export function parse(x: number): number;
+export function parse(x: any): number { return 0; }
+export abstract class Repo {
+  constructor(private readonly db: Db, name: string) {}
+  get size(): number { return 1; }
+  set size(v: number) {}
+  find(id: string): Row;
+  find(id: number): Row;
+  find(id: any): Row { return id; }
+  static async create(): Promise<Repo> { return null!; }
+  protected abstract close(): void;
+}
+"#;
+        let ir = skel(src, "repo.ts");
+        let parse: Vec<_> = ir.symbols.iter().filter(|s| s.name == "parse").collect();
+        assert_eq!(parse.len(), 1, "overloads are grouped");
+        let parse = parse[0];
+        assert_eq!(parse.overloads.len(), 3);
+        assert!(parse.overloads[2].implementation);
+        assert!(!parse.overloads[0].implementation);
+        assert!(parse.signature.contains("x: any"));
+        assert_eq!(parse.location.start_line, 1);
+        assert_eq!(parse.location.end_line, 3);
+
+        let repo = ir.symbols.iter().find(|s| s.name == "Repo").unwrap();
+        assert_eq!(repo.modifiers, vec!["abstract"]);
+        let ctor = ir.symbols.iter().find(|s| s.name == "Repo.constructor").unwrap();
+        assert_eq!(ctor.method_kind.as_deref(), Some("constructor"));
+        assert_eq!(ctor.parameter_properties, vec!["private readonly db: Db"]);
+        let size: Vec<_> = ir.symbols.iter().filter(|s| s.name == "Repo.size").collect();
+        let kinds: Vec<_> = size.iter().map(|s| s.method_kind.as_deref().unwrap()).collect();
+        assert_eq!(kinds, vec!["getter", "setter"]);
+        let find: Vec<_> = ir.symbols.iter().filter(|s| s.name == "Repo.find").collect();
+        assert_eq!(find.len(), 1);
+        assert_eq!(find[0].overloads.len(), 3);
+        let create = ir.symbols.iter().find(|s| s.name == "Repo.create").unwrap();
+        assert_eq!(create.modifiers, vec!["static", "async"]);
+        assert!(create.overloads.is_empty());
+        let close = ir.symbols.iter().find(|s| s.name == "Repo.close").unwrap();
+        assert_eq!(close.modifiers, vec!["abstract", "protected"]);
+        assert!(close.overloads.is_empty(), "a lone abstract method is no overload set");
+    }
+
+    #[test]
+    fn export_table_maps_specifiers_and_reexports() {
+        let src = "import { helper } from './h';\nfunction a() {}\nclass K { m() {} }\nexport { a as b, K };\nexport { helper };\nexport default a;\n";
+        let ir = skel(src, "mod.ts");
+        assert!(ir.symbols.iter().find(|s| s.name == "a").unwrap().exported);
+        assert!(ir.symbols.iter().find(|s| s.name == "K.m").unwrap().exported);
+        let b = ir.export_table.iter().find(|e| e.name == "b").unwrap();
+        assert_eq!(b.local.as_deref(), Some("a"));
+        assert!(b.source.is_none());
+        let d = ir.export_table.iter().find(|e| e.name == "default").unwrap();
+        assert_eq!(d.local.as_deref(), Some("a"));
+        let helper = ir.symbols.iter().find(|s| s.name == "helper").unwrap();
+        assert_eq!(helper.kind, "reexport");
+        assert_eq!(helper.signature, "export { helper } from \"./h\"");
+
+        let src = "import { helper as h } from './h';\nimport Dflt from './d';\nexport { h, Dflt };\n";
+        let ir = skel(src, "mod.ts");
+        let h = ir.symbols.iter().find(|s| s.name == "h").unwrap();
+        assert_eq!(h.signature, "export { helper as h } from \"./h\"");
+        let dflt = ir.symbols.iter().find(|s| s.name == "Dflt").unwrap();
+        assert_eq!(dflt.signature, "export { default as Dflt } from \"./d\"");
+
+        let barrel = "export { X, default as Y } from './x';\nexport * from './w';\nexport type * as ns from './z';\n";
+        let ir = skel(barrel, "index.ts");
+        let names: Vec<&str> = ir.export_table.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["X", "Y", "*", "ns"]);
+        let y = &ir.export_table[1];
+        assert_eq!(
+            (y.source.as_deref(), y.imported.as_deref()),
+            (Some("./x"), Some("default"))
+        );
+        assert!(ir.export_table[3].type_only);
+        let sigs: Vec<&str> = ir.symbols.iter().map(|s| s.signature.as_str()).collect();
+        assert_eq!(
+            sigs,
+            vec![
+                "export { X } from \"./x\"",
+                "export { default as Y } from \"./x\"",
+                "export type * as ns from \"./z\""
+            ]
+        );
+    }
+
     #[test]
     fn oversized_literals_are_elided_small_ones_kept() {
         let big: String = (0..100)
@@ -760,4 +1725,67 @@ const helper = () => 42;
             _ => panic!("expected NotFound"),
         }
     }
+
+    #[test]
+    fn get_implementation_follows_nested_dotted_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let f = dir.path().join("nested.ts");
+        let src = r#"export namespace Utils {
+  export function format(x: number): string { return String(x); }
+  export namespace Inner.Deep { export const z = 1; }
+}
+export const api = {
+  fetchUser(id: string) { return id; },
+  nested: { ping: () => 1 },
+};
+class Box {
+  static count = 0;
+  get size(): number { return 1; }
+  set size(v: number) {}
+  #secret = 2;
+  static { Box.count = 1; }
+}
+interface Props { onChange(v: string): void; style: { color: string } }
+enum Color { Red = 1, Blue }
+export namespace Utils { export const extra = 2; }
+namespace Box { export const kind = "box"; }
+declare module "a.b" { export function load(): void; }
+"#;
+        std::fs::write(&f, src).unwrap();
+        let found = |target: &str| match get_implementation(&f, target).unwrap() {
+            ImplLookup::Found(s) => s,
+            _ => panic!("expected Found for {}", target),
+        };
+        assert!(found("Utils.format").starts_with("export function format"));
+        assert_eq!(found("Utils.Inner.Deep.z"), "export const z = 1;");
+        assert_eq!(found("api.fetchUser"), "fetchUser(id: string) { return id; }");
+        assert_eq!(found("api.nested.ping"), "ping: () => 1");
+        assert_eq!(
+            found("Box.size"),
+            "get size(): number { return 1; }\nset size(v: number) {}"
+        );
+        assert_eq!(found("Box.#secret"), "#secret = 2;");
+        assert_eq!(found("Box.static"), "static { Box.count = 1; }");
+        assert_eq!(found("Props.onChange"), "onChange(v: string): void;");
+        assert_eq!(found("Props.style.color"), "color: string");
+        assert_eq!(found("Color.Blue"), "Blue");
+        assert_eq!(found("Utils.extra"), "export const extra = 2;");
+        assert_eq!(found("Box.kind"), "export const kind = \"box\";");
+        assert_eq!(found("\"a.b\".load"), "export function load(): void;");
+
+        match get_implementation(&f, "Utils.nope").unwrap() {
+            ImplLookup::ChildNotFound { parent, children } => {
+                assert_eq!(parent, "Utils");
+                assert_eq!(children, vec!["format".to_string(), "Inner".to_string(), "extra".to_string()]);
+            }
+            _ => panic!("expected ChildNotFound"),
+        }
+        match get_implementation(&f, "Utils.format.x").unwrap() {
+            ImplLookup::ChildNotFound { parent, children } => {
+                assert_eq!(parent, "Utils.format");
+                assert!(children.is_empty());
+            }
+            _ => panic!("expected ChildNotFound"),
+        }
+    }
 }
@@ -0,0 +1,1297 @@
+//! Cross-file symbol resolution over the per-file export tables.
+//!
+//! Follows `export { X } from`, `export *` and imports re-exported by name
+//! through the graph to the file that actually declares a symbol. Everything
+//! here is syntactic: edges come from `import_records` and their resolved
+//! keys, never from type information. The cross-file caller/callee queries
+//! build on the same resolution to link each file's `imported_calls`, and
+//! component props types imported from another file are resolved through it.
+
+use ignore::gitignore::Gitignore;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use crate::calls::ImportedCall;
+use crate::components::{self, ComponentRecord};
+use crate::graph::{key_matches, AppState};
+use crate::resolve::Resolution;
+use crate::skeleton::{self, ExportEntry, FileSkeleton, Location, SymbolInfo};
+
+/// One step of a re-export chain: `name` as exported by `file`, and how the
+/// chain continues from there.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Hop {
+    pub file: String,
+    pub name: String,
+    /// local | reexport | export_star | import | namespace
+    pub via: &'static str,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Definition {
+    pub file: String,
+    pub symbol: SymbolInfo,
+    pub chain: Vec<Hop>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExportResolution {
+    /// Declared in `definition.file`.
+    Defined(Definition),
+    /// The name is a whole module namespace (`export * as ns`,
+    /// `import * as ns; export { ns }`).
+    Namespace { file: String, chain: Vec<Hop> },
+    /// Forwarded from a package outside the graph.
+    External {
+        package: String,
+        name: String,
+        chain: Vec<Hop>,
+    },
+    /// Two or more `export *` sources provide the name; per the module spec
+    /// such a name is not exported at all.
+    Ambiguous {
+        chain: Vec<Hop>,
+        candidates: Vec<Definition>,
+    },
+    NotFound { chain: Vec<Hop>, reason: String },
+}
+
+/// Follow `name` as exported by `key` to its declaring file and symbol.
+pub fn resolve_export(state: &AppState, key: &str, name: &str) -> ExportResolution {
+    ExportWalk {
+        state,
+        visited: HashSet::new(),
+        memo: HashMap::new(),
+    }
+    .resolve_in(key, name)
+}
+
+impl ExportResolution {
+    /// The same resolution as reached through `hop`.
+    fn prefixed(mut self, hop: Hop) -> Self {
+        match &mut self {
+            ExportResolution::Defined(def) => def.chain.insert(0, hop),
+            ExportResolution::Ambiguous { chain, candidates } => {
+                for candidate in candidates {
+                    candidate.chain.insert(0, hop.clone());
+                }
+                chain.insert(0, hop);
+            }
+            ExportResolution::Namespace { chain, .. }
+            | ExportResolution::External { chain, .. }
+            | ExportResolution::NotFound { chain, .. } => chain.insert(0, hop),
+        }
+        self
+    }
+
+    /// Why `name` did not resolve to a single declaration; `None` when it did.
+    pub fn failure(&self, name: &str) -> Option<String> {
+        match self {
+            ExportResolution::Defined(_) => None,
+            ExportResolution::Namespace { file, .. } => Some(format!(
+                "'{}' is a namespace re-export of {}; query its members there",
+                name, file
+            )),
+            ExportResolution::External { package, .. } => {
+                Some(format!("'{}' is forwarded from package '{}'", name, package))
+            }
+            ExportResolution::Ambiguous { candidates, .. } => Some(format!(
+                "'{}' is ambiguous: provided by {} through export *",
+                name,
+                candidates
+                    .iter()
+                    .map(|d| d.file.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            ExportResolution::NotFound { reason, .. } => Some(reason.clone()),
+        }
+    }
+}
+
+fn not_found(chain: Vec<Hop>, reason: String) -> ExportResolution {
+    ExportResolution::NotFound { chain, reason }
+}
+
+/// One `resolve_export` walk. Results are memoized per `(file, name)` with
+/// chains starting at that file, so layered `export *` diamonds are walked
+/// once; `visited` holds every pair entered, and one entered but not yet
+/// finished is a cycle.
+struct ExportWalk<'s> {
+    state: &'s AppState,
+    visited: HashSet<(String, String)>,
+    memo: HashMap<(String, String), ExportResolution>,
+}
+
+impl ExportWalk<'_> {
+    /// Continue into the module `source` (as written in `node`), looking up
+    /// `imported` there.
+    fn follow_source(
+        &mut self,
+        key: &str,
+        node: &FileSkeleton,
+        source: &str,
+        imported: &str,
+    ) -> ExportResolution {
+        let resolved = node
+            .import_records
+            .iter()
+            .find(|r| r.source == source)
+            .and_then(|r| r.resolved.clone());
+        match resolved {
+            Some(target) if imported == "*" => ExportResolution::Namespace {
+                file: target,
+                chain: Vec::new(),
+            },
+            Some(target) => self.resolve_in(&target, imported),
+            None => match self.state.resolver.resolve(&self.state.root, key, source) {
+                Resolution::External(package) => ExportResolution::External {
+                    package,
+                    name: imported.to_string(),
+                    chain: Vec::new(),
+                },
+                _ => not_found(Vec::new(), format!("cannot resolve '{}' from {}", source, key)),
+            },
+        }
+    }
+
+    fn resolve_in(&mut self, key: &str, name: &str) -> ExportResolution {
+        let id = (key.to_string(), name.to_string());
+        if let Some(done) = self.memo.get(&id) {
+            return done.clone();
+        }
+        if !self.visited.insert(id.clone()) {
+            return not_found(Vec::new(), format!("re-export cycle at {}#{}", key, name));
+        }
+        let result = self.resolve_uncached(key, name);
+        self.memo.insert(id, result.clone());
+        result
+    }
+
+    fn resolve_uncached(&mut self, key: &str, name: &str) -> ExportResolution {
+        let state = self.state;
+        let Some(node) = state.skeleton_graph.get(key) else {
+            return not_found(Vec::new(), format!("{} is not in the graph", key));
+        };
+        let hop = |via| Hop {
+            file: key.to_string(),
+            name: name.to_string(),
+            via,
+        };
+
+        // Explicit exports shadow anything provided through `export *`.
+        if let Some(entry) = node
+            .export_table
+            .iter()
+            .find(|e| e.name == name && e.name != "*")
+        {
+            if let Some(source) = &entry.source {
+                let imported = entry.imported.as_deref().unwrap_or(name);
+                let via = if imported == "*" { "namespace" } else { "reexport" };
+                return self.follow_source(key, &node, source, imported).prefixed(hop(via));
+            }
+            let local = entry.local.as_deref().unwrap_or(name);
+            if let Some(sym) = node
+                .symbols
+                .iter()
+                .find(|s| s.name == local && s.kind != "reexport")
+            {
+                return ExportResolution::Defined(Definition {
+                    file: key.to_string(),
+                    symbol: sym.clone(),
+                    chain: vec![hop("local")],
+                });
+            }
+            // An import exported onwards by name: `import { a } from; export { a }`.
+            let import = node.import_records.iter().find_map(|r| {
+                r.bindings
+                    .iter()
+                    .find(|b| b.local == local)
+                    .map(|b| (r.source.clone(), b.imported.clone()))
+            });
+            return match import {
+                Some((source, imported)) => self
+                    .follow_source(key, &node, &source, &imported)
+                    .prefixed(hop("import")),
+                None => not_found(
+                    Vec::new(),
+                    format!("{} exports '{}' but declares no '{}'", key, name, local),
+                ),
+            };
+        }
+
+        // `export *` never forwards `default`.
+        if name == "default" {
+            return not_found(Vec::new(), format!("{} has no default export", key));
+        }
+
+        let mut found = Vec::new();
+        let mut namespaces = Vec::new();
+        let mut external = None;
+        for entry in node.export_table.iter().filter(|e| e.name == "*") {
+            let Some(source) = &entry.source else {
+                continue;
+            };
+            match self
+                .follow_source(key, &node, source, name)
+                .prefixed(hop("export_star"))
+            {
+                ExportResolution::Defined(def) => found.push(def),
+                ExportResolution::Ambiguous { candidates, .. } => found.extend(candidates),
+                ns @ ExportResolution::Namespace { .. } => namespaces.push(ns),
+                ext @ ExportResolution::External { .. } => external = external.or(Some(ext)),
+                ExportResolution::NotFound { .. } => {}
+            }
+        }
+
+        // The same declaration reached through two stars (diamond barrels) is
+        // not a collision.
+        let mut seen = HashSet::new();
+        found.retain(|d| seen.insert((d.file.clone(), d.symbol.name.clone())));
+        // A package star is only the answer when no in-graph branch provides
+        // the name.
+        match (found.len(), namespaces.len()) {
+            (1, 0) => ExportResolution::Defined(found.remove(0)),
+            (0, 1) => namespaces.remove(0),
+            (0, 0) => external.unwrap_or_else(|| {
+                not_found(Vec::new(), format!("{} does not export '{}'", key, name))
+            }),
+            _ => ExportResolution::Ambiguous {
+                chain: Vec::new(),
+                candidates: found,
+            },
+        }
+    }
+}
+
+/// One import (or re-export) of a symbol by another file.
+#[derive(Serialize, Debug, Clone)]
+pub struct Reference {
+    pub file: String,
+    /// Name bound in `file`; for re-exports, the name exported onwards.
+    pub local: String,
+    /// Name taken from the imported module (`default`, `*`, or an export).
+    pub imported: String,
+    /// named | default | namespace | dynamic | reexport
+    pub kind: &'static str,
+    pub type_only: bool,
+    /// The import / re-export statement.
+    pub location: Location,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub usages: Vec<Location>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct References {
+    pub definition: Definition,
+    pub references: Vec<Reference>,
+}
+
+fn same_definition(a: &Definition, b: &Definition) -> bool {
+    a.file == b.file && a.symbol.name == b.symbol.name && a.symbol.location == b.symbol.location
+}
+
+/// Every file importing the symbol that `key` exports as `name`, directly or
+/// through barrels. Candidates are the transitive dependents of the declaring
+/// file; each import binding is checked by resolving it back to the
+/// definition, so aliases and renaming re-exports are followed. With
+/// `usages`, importing files are parsed for identifier references.
+pub fn find_references(
+    state: &AppState,
+    key: &str,
+    name: &str,
+    usages: bool,
+) -> Result<References, String> {
+    let definition = match resolve_export(state, key, name) {
+        ExportResolution::Defined(def) => def,
+        other => return Err(other.failure(name).unwrap_or_default()),
+    };
+
+    let mut candidates = BTreeSet::new();
+    let mut queue = VecDeque::from([definition.file.clone()]);
+    while let Some(next) = queue.pop_front() {
+        for dependent in state.dependents_of(&next) {
+            if candidates.insert(dependent.clone()) {
+                queue.push_back(dependent);
+            }
+        }
+    }
+
+    let matches = |target: &str, imported: &str| {
+        matches!(
+            resolve_export(state, target, imported),
+            ExportResolution::Defined(ref d) if same_definition(d, &definition)
+        )
+    };
+    // Export name under which `target` exposes the definition, for
+    // namespace bindings (`ns.<member>`).
+    let exposed_as = |target: &str| -> Option<String> {
+        let node = state.skeleton_graph.get(target)?;
+        let mut names: Vec<String> = node
+            .export_table
+            .iter()
+            .filter(|e| e.name != "*")
+            .map(|e| e.name.clone())
+            .collect();
+        names.push(definition.symbol.name.clone());
+        drop(node);
+        names.into_iter().find(|n| matches(target, n))
+    };
+
+    let mut references = Vec::new();
+    for file in candidates {
+        let Some(node) = state.skeleton_graph.get(&file).map(|n| n.clone()) else {
+            continue;
+        };
+        for record in &node.import_records {
+            let Some(target) = &record.resolved else {
+                continue;
+            };
+            for binding in &record.bindings {
+                let reexport = node.export_table.iter().any(|e| {
+                    e.source.as_deref() == Some(record.source.as_str())
+                        && e.name == binding.local
+                        && e.imported.as_deref() == Some(binding.imported.as_str())
+                });
+                let (kind, member) = match binding.imported.as_str() {
+                    "*" if record.dynamic => match exposed_as(target) {
+                        Some(_) => ("dynamic", None),
+                        None => continue,
+                    },
+                    "*" => match exposed_as(target) {
+                        Some(member) if reexport => ("reexport", Some(member)),
+                        Some(member) => ("namespace", Some(member)),
+                        None => continue,
+                    },
+                    imported if !matches(target, imported) => continue,
+                    _ if reexport => ("reexport", None),
+                    "default" => ("default", None),
+                    _ => ("named", None),
+                };
+                let usages = if usages && !reexport && !record.dynamic {
+                    skeleton::find_usages(&state.abs_path(&file), &[(&binding.local, member.as_deref())])
+                        .map(|mut found| found.remove(0))
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+                references.push(Reference {
+                    file: file.clone(),
+                    local: binding.local.clone(),
+                    imported: binding.imported.clone(),
+                    kind,
+                    type_only: binding.type_only,
+                    location: record.location,
+                    usages,
+                });
+            }
+        }
+    }
+    Ok(References {
+        definition,
+        references,
+    })
+}
+
+/// A file consuming one export of another, from the symbol-level index.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolImporter {
+    pub file: String,
+    /// import (named or default binding) | namespace | dynamic | reexport
+    pub via: &'static str,
+}
+
+/// Files consuming the symbol `key` exports as `name`, at symbol granularity:
+/// importers of other exports of the same file are excluded. The name is
+/// first resolved to its declaration; re-exporting barrels are reported and
+/// then expanded to their own importers of the forwarded name.
+pub fn symbol_importers(state: &AppState, key: &str, name: &str) -> Vec<SymbolImporter> {
+    let start = match resolve_export(state, key, name) {
+        ExportResolution::Defined(def) => {
+            let exported = def.chain.last().map_or(name.to_string(), |h| h.name.clone());
+            (def.file, exported)
+        }
+        _ => (key.to_string(), name.to_string()),
+    };
+
+    let mut found = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some((file, export)) = queue.pop_front() {
+        if !visited.insert((file.clone(), export.clone())) {
+            continue;
+        }
+        let mut consumers = state.symbol_dependents_of(&file, &export);
+        consumers.extend(state.symbol_dependents_of(&file, "*"));
+        for consumer in consumers {
+            let Some(node) = state.skeleton_graph.get(&consumer).map(|n| n.clone()) else {
+                continue;
+            };
+            let records: Vec<_> = node
+                .import_records
+                .iter()
+                .filter(|r| r.resolved.as_deref() == Some(file.as_str()))
+                .collect();
+            let source_of = |e: &ExportEntry| {
+                records
+                    .iter()
+                    .any(|r| e.source.as_deref() == Some(r.source.as_str()))
+            };
+            let mut forwarded = Vec::new();
+            let mut uses = None;
+            for record in &records {
+                for binding in &record.bindings {
+                    let reexport = node.export_table.iter().find(|e| {
+                        source_of(e)
+                            && e.name == binding.local
+                            && e.imported.as_deref() == Some(binding.imported.as_str())
+                    });
+                    match (binding.imported.as_str(), reexport) {
+                        ("*", Some(e)) if e.name == "*" => {
+                            // `export *` forwards everything but `default`, and
+                            // loses to an explicit export of the same name.
+                            let shadowed = node
+                                .export_table
+                                .iter()
+                                .any(|x| x.name == export && x.source.is_none());
+                            if export != "default" && !shadowed {
+                                forwarded.push(export.clone());
+                            }
+                        }
+                        ("*", Some(_)) => uses = uses.or(Some("reexport")),
+                        ("*", None) if record.dynamic => uses = uses.or(Some("dynamic")),
+                        ("*", None) => uses = Some("namespace"),
+                        (imported, Some(e)) if imported == export => forwarded.push(e.name.clone()),
+                        (imported, None) if imported == export => {
+                            uses = Some(uses.unwrap_or("import"));
+                            // `import { a }; export { a as b }` forwards too.
+                            forwarded.extend(
+                                node.export_table
+                                    .iter()
+                                    .filter(|e| {
+                                        e.source.is_none()
+                                            && e.local.as_deref() == Some(binding.local.as_str())
+                                    })
+                                    .map(|e| e.name.clone()),
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !forwarded.is_empty() {
+                found.insert(SymbolImporter {
+                    file: consumer.clone(),
+                    via: "reexport",
+                });
+                queue.extend(forwarded.into_iter().map(|n| (consumer.clone(), n)));
+            }
+            if let Some(via) = uses {
+                found.insert(SymbolImporter {
+                    file: consumer,
+                    via,
+                });
+            }
+        }
+    }
+    found.into_iter().collect()
+}
+
+/// An exported declaration no other file consumes.
+#[derive(Serialize, Debug, Clone)]
+pub struct DeadExport {
+    pub file: String,
+    pub name: String,
+    pub kind: String,
+    pub location: Location,
+    /// high | medium | low
+    pub confidence: &'static str,
+    pub notes: Vec<String>,
+}
+
+/// Exports declared in a file that nothing in the graph imports, directly,
+/// through barrels, via namespace imports or string-literal `import()`.
+/// Files matching `entrypoints` are treated as consumed from outside the
+/// graph, as are names forwarded by a barrel that is itself an entrypoint.
+pub fn dead_exports(state: &AppState, entrypoints: &Gitignore) -> Vec<DeadExport> {
+    let mut keys: Vec<String> = state.skeleton_graph.iter().map(|e| e.key().clone()).collect();
+    keys.sort();
+
+    let mut dead = Vec::new();
+    for key in keys {
+        if key_matches(entrypoints, &key) {
+            continue;
+        }
+        let Some(node) = state.skeleton_graph.get(&key).map(|n| n.clone()) else {
+            continue;
+        };
+        let orphan = state.dependents_of(&key).is_empty();
+        // Dead exports of this file with their local names, checked for
+        // internal uses in one parse once all are known.
+        let mut pending: Vec<(DeadExport, &str)> = Vec::new();
+        for entry in node
+            .export_table
+            .iter()
+            .filter(|e| e.source.is_none() && e.name != "*")
+        {
+            // Only declarations live here; imports exported onwards are
+            // judged where they are declared.
+            let ExportResolution::Defined(def) = resolve_export(state, &key, &entry.name) else {
+                continue;
+            };
+            if def.file != key {
+                continue;
+            }
+            let importers = symbol_importers(state, &key, &entry.name);
+            if importers.iter().any(|i| i.via != "reexport") {
+                continue;
+            }
+            let barrels: Vec<&str> = importers.iter().map(|i| i.file.as_str()).collect();
+            if barrels.iter().any(|b| key_matches(entrypoints, b)) {
+                continue;
+            }
+
+            let mut notes = Vec::new();
+            let confidence = if !barrels.is_empty() {
+                notes.push(format!(
+                    "re-exported by {} but never imported from there; if that barrel is a package entry, add it to entrypoints",
+                    barrels.join(", ")
+                ));
+                "medium"
+            } else if orphan {
+                notes.push(format!(
+                    "no file imports {}; if it is loaded by a framework or tooling, add it to entrypoints",
+                    key
+                ));
+                "low"
+            } else {
+                "high"
+            };
+            if entry.type_only || matches!(def.symbol.kind.as_str(), "interface" | "type") {
+                notes.push("type-only export".to_string());
+            }
+            let local = entry.local.as_deref().unwrap_or(&entry.name);
+            pending.push((
+                DeadExport {
+                    file: key.clone(),
+                    name: entry.name.clone(),
+                    kind: def.symbol.kind.clone(),
+                    location: def.symbol.location,
+                    confidence,
+                    notes,
+                },
+                local,
+            ));
+        }
+        let targets: Vec<(&str, Option<&str>)> = pending
+            .iter()
+            .map(|(_, local)| (*local, None))
+            .filter(|(local, _)| *local != "default")
+            .collect();
+        let used: HashSet<&str> = if targets.is_empty() {
+            HashSet::new()
+        } else {
+            skeleton::find_usages(&state.abs_path(&key), &targets)
+                .map(|found| {
+                    targets
+                        .iter()
+                        .zip(found)
+                        .filter(|(_, usages)| !usages.is_empty())
+                        .map(|((local, _), _)| *local)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        for (mut export, local) in pending {
+            if used.contains(local) {
+                export.notes.push(format!(
+                    "'{}' is used inside {}; only the export is unused",
+                    local, key
+                ));
+            }
+            dead.push(export);
+        }
+    }
+    dead
+}
+
+/// One hop of a caller/callee query.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    pub file: String,
+    /// The calling node for `callers`, the called one for `callees`.
+    pub node: String,
+    /// Lines of the calls, in the calling file.
+    pub lines: Vec<u32>,
+    /// 1 for direct calls, 2 for calls of those, and so on.
+    pub depth: usize,
+    /// The edge crosses an import.
+    pub cross_file: bool,
+}
+
+/// The declaration an imported call lands on: the binding's graph edge, then
+/// `resolve_export` through any barrels. `None` for packages and names that
+/// do not resolve to a single declaration.
+fn imported_target(state: &AppState, node: &FileSkeleton, call: &ImportedCall) -> Option<(String, String)> {
+    let (record, binding) = node.import_records.iter().filter(|r| !r.dynamic).find_map(|r| {
+        r.bindings
+            .iter()
+            .find(|b| b.local == call.local)
+            .map(|b| (r, b))
+    })?;
+    let target = record.resolved.as_deref()?;
+    let name = match binding.imported.as_str() {
+        "*" => call.member.as_deref()?,
+        imported => imported,
+    };
+    match resolve_export(state, target, name) {
+        ExportResolution::Defined(def) => Some((def.file, def.symbol.name)),
+        _ => None,
+    }
+}
+
+type Hops = BTreeMap<(String, String), (Vec<u32>, bool)>;
+
+fn add_hop(hops: &mut Hops, file: &str, node: &str, lines: &[u32], cross_file: bool) {
+    let entry = hops
+        .entry((file.to_string(), node.to_string()))
+        .or_insert_with(|| (Vec::new(), cross_file));
+    entry.0.extend_from_slice(lines);
+    entry.0.sort_unstable();
+    entry.0.dedup();
+}
+
+/// Breadth-first walk over call edges from `(key, node)`, `step` producing the
+/// neighbours of one node. Each node is reported once, at its shallowest
+/// depth.
+fn walk_calls(
+    state: &AppState,
+    key: &str,
+    node: &str,
+    depth: usize,
+    step: impl Fn(&AppState, &str, &str, &mut Hops),
+) -> Vec<CallSite> {
+    let mut seen = HashSet::from([(key.to_string(), node.to_string())]);
+    let mut frontier = vec![(key.to_string(), node.to_string())];
+    let mut out = Vec::new();
+    for level in 1..=depth {
+        let mut hops = Hops::new();
+        for (file, name) in &frontier {
+            step(state, file, name, &mut hops);
+        }
+        frontier.clear();
+        for ((file, name), (lines, cross_file)) in hops {
+            if seen.insert((file.clone(), name.clone())) {
+                frontier.push((file.clone(), name.clone()));
+                out.push(CallSite {
+                    file,
+                    node: name,
+                    lines,
+                    depth: level,
+                    cross_file,
+                });
+            }
+        }
+        if frontier.is_empty() {
+            break;
+        }
+    }
+    out
+}
+
+/// What `node` in `key` calls, transitively up to `depth`: same-file edges
+/// from `call_graph` plus imported calls resolved to their declarations.
+pub fn callees(state: &AppState, key: &str, node: &str, depth: usize) -> Vec<CallSite> {
+    walk_calls(state, key, node, depth, |state, file, name, hops| {
+        let Some(skel) = state.skeleton_graph.get(file).map(|n| n.clone()) else {
+            return;
+        };
+        for edge in skel.call_graph.iter().filter(|e| e.caller == name) {
+            add_hop(hops, file, &edge.callee, &edge.lines, false);
+        }
+        for call in skel.imported_calls.iter().filter(|c| c.caller == name) {
+            if let Some((target, symbol)) = imported_target(state, &skel, call) {
+                add_hop(hops, &target, &symbol, &call.lines, true);
+            }
+        }
+    })
+}
+
+/// Who calls `node` in `key`, transitively up to `depth`: same-file callers
+/// plus, when `node` is exported, the nodes of importing files (found through
+/// the symbol-level index, barrels included) whose imported calls resolve
+/// back to it.
+pub fn callers(state: &AppState, key: &str, node: &str, depth: usize) -> Vec<CallSite> {
+    walk_calls(state, key, node, depth, |state, file, name, hops| {
+        let Some(skel) = state.skeleton_graph.get(file).map(|n| n.clone()) else {
+            return;
+        };
+        for edge in skel.call_graph.iter().filter(|e| e.callee == name) {
+            add_hop(hops, file, &edge.caller, &edge.lines, false);
+        }
+        let target = (file.to_string(), name.to_string());
+        let importers: BTreeSet<String> = skel
+            .export_table
+            .iter()
+            .filter(|e| e.source.is_none() && e.local.as_deref().unwrap_or(&e.name) == name)
+            .flat_map(|e| symbol_importers(state, file, &e.name))
+            .filter(|i| i.via != "reexport")
+            .map(|i| i.file)
+            .collect();
+        for importer in importers {
+            let Some(other) = state.skeleton_graph.get(&importer).map(|n| n.clone()) else {
+                continue;
+            };
+            for call in &other.imported_calls {
+                if imported_target(state, &other, call).as_ref() == Some(&target) {
+                    add_hop(hops, &importer, &call.caller, &call.lines, true);
+                }
+            }
+        }
+    })
+}
+
+/// A symbol whose signature references a type.
+#[derive(Serialize, Debug, Clone)]
+pub struct TypeUsage {
+    pub file: String,
+    pub symbol: String,
+    pub kind: String,
+    pub exported: bool,
+    /// The reference as written in that file (`User`, `Alias`, `ns.User`).
+    pub written_as: String,
+    pub location: Location,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TypeUsages {
+    pub definition: Definition,
+    pub usages: Vec<TypeUsage>,
+}
+
+/// Where a type name written in `node`'s signatures is declared: a local
+/// declaration, or an import (named, or `ns.T` on a namespace import)
+/// followed through `resolve_export`.
+fn type_target(state: &AppState, key: &str, node: &FileSkeleton, written: &str) -> Option<(String, String)> {
+    let (head, member) = match written.split_once('.') {
+        Some((head, member)) => (head, Some(member)),
+        None => (written, None),
+    };
+    if member.is_none()
+        && node
+            .symbols
+            .iter()
+            .any(|s| s.name == written && s.kind != "reexport")
+    {
+        return Some((key.to_string(), written.to_string()));
+    }
+    let (record, binding) = node.import_records.iter().filter(|r| !r.dynamic).find_map(|r| {
+        r.bindings.iter().find(|b| b.local == head).map(|b| (r, b))
+    })?;
+    let target = record.resolved.as_deref()?;
+    let export = match (binding.imported.as_str(), member) {
+        ("*", Some(member)) => member,
+        (imported, None) if imported != "*" => imported,
+        _ => return None,
+    };
+    match resolve_export(state, target, export) {
+        ExportResolution::Defined(def) => Some((def.file, def.symbol.name)),
+        _ => None,
+    }
+}
+
+/// Symbols whose signatures (`type_refs`) reference the type `key` exports
+/// as `name` — or declares locally, for same-file questions. Candidates are
+/// the declaring file and the files importing that export, barrels
+/// included. Unless `include_internal`, only exported, non-private symbols
+/// count: the type's footprint on public signatures.
+pub fn find_type_usages(
+    state: &AppState,
+    key: &str,
+    name: &str,
+    include_internal: bool,
+) -> Result<TypeUsages, String> {
+    let definition = match resolve_export(state, key, name) {
+        ExportResolution::Defined(def) => def,
+        other => {
+            let local = state.skeleton_graph.get(key).and_then(|n| {
+                n.symbols
+                    .iter()
+                    .find(|s| s.name == name && s.kind != "reexport")
+                    .cloned()
+            });
+            match local {
+                Some(symbol) => Definition {
+                    file: key.to_string(),
+                    symbol,
+                    chain: Vec::new(),
+                },
+                None => return Err(other.failure(name).unwrap_or_default()),
+            }
+        }
+    };
+    let target = (definition.file.clone(), definition.symbol.name.clone());
+    let exported_as = definition
+        .chain
+        .last()
+        .map_or(definition.symbol.name.clone(), |h| h.name.clone());
+
+    let mut files = BTreeSet::from([definition.file.clone()]);
+    files.extend(
+        symbol_importers(state, &definition.file, &exported_as)
+            .into_iter()
+            .map(|i| i.file),
+    );
+
+    let mut usages = Vec::new();
+    for file in files {
+        let Some(node) = state.skeleton_graph.get(&file).map(|n| n.clone()) else {
+            continue;
+        };
+        for sym in &node.symbols {
+            let public = sym.exported && !sym.modifiers.iter().any(|m| m == "private");
+            if (!include_internal && !public) || (file == target.0 && sym.name == target.1) {
+                continue;
+            }
+            if let Some(written) = sym
+                .type_refs
+                .iter()
+                .find(|r| type_target(state, &file, &node, r).as_ref() == Some(&target))
+            {
+                usages.push(TypeUsage {
+                    file: file.clone(),
+                    symbol: sym.name.clone(),
+                    kind: sym.kind.clone(),
+                    exported: sym.exported,
+                    written_as: written.clone(),
+                    location: sym.location,
+                });
+            }
+        }
+    }
+    Ok(TypeUsages { definition, usages })
+}
+
+/// A component record tagged with the graph key that declares it.
+#[derive(Serialize, Debug, Clone)]
+pub struct FileComponent {
+    pub file: String,
+    #[serde(flatten)]
+    pub component: ComponentRecord,
+}
+
+/// Component records of `key` (or of every file, sorted by key), with props
+/// types imported from another file resolved to their declaration and its
+/// fields. Each declaring file is parsed once for all the types looked up
+/// in it.
+pub fn components(state: &AppState, key: Option<&str>) -> Vec<FileComponent> {
+    let mut keys: Vec<String> = match key {
+        Some(k) => vec![k.to_string()],
+        None => state.skeleton_graph.iter().map(|e| e.key().clone()).collect(),
+    };
+    keys.sort();
+    let mut out = Vec::new();
+    // Declaring file -> props type name -> indices into `out` to fill in.
+    let mut imported: BTreeMap<String, BTreeMap<String, Vec<usize>>> = BTreeMap::new();
+    for file in keys {
+        let Some(node) = state.skeleton_graph.get(&file) else {
+            continue;
+        };
+        for mut component in node.components.iter().cloned() {
+            if let Some(props) = &mut component.props
+                && props.fields.is_empty()
+                && props.extends.is_empty()
+                && let Some(written) = &props.type_name
+            {
+                let bare = written.split('<').next().unwrap_or(written);
+                if let Some((def_file, def_name)) = type_target(state, &file, &node, bare)
+                    && def_file != file
+                {
+                    imported
+                        .entry(def_file.clone())
+                        .or_default()
+                        .entry(def_name)
+                        .or_default()
+                        .push(out.len());
+                    props.defined_in = Some(def_file);
+                }
+            }
+            out.push(FileComponent {
+                file: file.clone(),
+                component,
+            });
+        }
+    }
+    for (def_file, names) in imported {
+        let wanted: Vec<&str> = names.keys().map(String::as_str).collect();
+        let Ok(declared) = components::props_from_file(&state.abs_path(&def_file), &wanted) else {
+            continue;
+        };
+        for (name, indices) in names {
+            let Some(found) = declared.get(&name) else {
+                continue;
+            };
+            for i in indices {
+                if let Some(props) = &mut out[i].component.props {
+                    props.fields = found.fields.clone();
+                    props.extends = found.extends.clone();
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::graph::perform_initial_sweep;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    /// Writes `files` into a temp root and sweeps them into a fresh graph.
+    pub fn project(files: &[(&str, &str)]) -> (tempfile::TempDir, Arc<AppState>) {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        for (path, src) in files {
+            let abs = root.join(path);
+            std::fs::create_dir_all(abs.parent().unwrap_or(Path::new("."))).unwrap();
+            std::fs::write(abs, src).unwrap();
+        }
+        let state = Arc::new(AppState::new(root));
+        perform_initial_sweep(&state);
+        (dir, state)
+    }
+
+    #[test]
+    fn follows_star_and_named_reexports_to_the_declaration() {
+        let (_dir, state) = project(&[
+            ("src/index.ts", "export * from './ui';\nexport { api as client } from './net/api';\n"),
+            ("src/ui/index.ts", "export * from './button';\nimport { Icon } from './icon';\nexport { Icon };\n"),
+            ("src/ui/button.tsx", "export const Button = () => null;\n"),
+            ("src/ui/icon.tsx", "export function Icon() { return null; }\n"),
+            ("src/net/api.ts", "const api = {};\nexport { api };\n"),
+        ]);
+
+        match resolve_export(&state, "src/index.ts", "Button") {
+            ExportResolution::Defined(def) => {
+                assert_eq!(def.file, "src/ui/button.tsx");
+                assert_eq!(def.symbol.name, "Button");
+                let files: Vec<&str> = def.chain.iter().map(|h| h.file.as_str()).collect();
+                assert_eq!(files, vec!["src/index.ts", "src/ui/index.ts", "src/ui/button.tsx"]);
+                assert_eq!(def.chain[0].via, "export_star");
+            }
+            other => panic!("expected Defined, got {:?}", other),
+        }
+        match resolve_export(&state, "src/index.ts", "Icon") {
+            ExportResolution::Defined(def) => {
+                assert_eq!(def.file, "src/ui/icon.tsx");
+                assert_eq!(def.chain[1].via, "import");
+            }
+            other => panic!("expected Defined, got {:?}", other),
+        }
+        match resolve_export(&state, "src/index.ts", "client") {
+            ExportResolution::Defined(def) => {
+                assert_eq!(def.file, "src/net/api.ts");
+                assert_eq!(def.symbol.name, "api");
+                assert_eq!(def.chain[0].via, "reexport");
+            }
+            other => panic!("expected Defined, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve_export(&state, "src/index.ts", "Nope"),
+            ExportResolution::NotFound { .. }
+        ));
+    }
+
+    #[test]
+    fn detects_star_collisions_and_namespaces() {
+        let (_dir, state) = project(&[
+            ("src/index.ts", "export * from './a';\nexport * from './b';\nexport * as b from './b';\nexport * from 'pkg';\n"),
+            ("src/a.ts", "export const dup = 1;\nexport const onlyA = 1;\n"),
+            ("src/b.ts", "export const dup = 2;\n"),
+        ]);
+        match resolve_export(&state, "src/index.ts", "dup") {
+            ExportResolution::Ambiguous { candidates, .. } => {
+                let mut files: Vec<&str> = candidates.iter().map(|d| d.file.as_str()).collect();
+                files.sort();
+                assert_eq!(files, vec!["src/a.ts", "src/b.ts"]);
+            }
+            other => panic!("expected Ambiguous, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve_export(&state, "src/index.ts", "b"),
+            ExportResolution::Namespace { ref file, .. } if file == "src/b.ts"
+        ));
+        assert!(matches!(
+            resolve_export(&state, "src/index.ts", "elsewhere"),
+            ExportResolution::External { ref package, .. } if package == "pkg"
+        ));
+    }
+
+    #[test]
+    fn layered_star_diamonds_resolve_without_rewalking() {
+        const LAYERS: usize = 24;
+        let mut files: Vec<(String, String)> = Vec::new();
+        for i in 0..LAYERS {
+            for side in ["a", "b"] {
+                files.push((
+                    format!("src/{}{}.ts", side, i),
+                    format!("export * from './a{}';\nexport * from './b{}';\n", i + 1, i + 1),
+                ));
+            }
+        }
+        files.push((format!("src/a{}.ts", LAYERS), "export const leaf = 1;\n".to_string()));
+        files.push((format!("src/b{}.ts", LAYERS), "export {};\n".to_string()));
+        let files: Vec<(&str, &str)> = files.iter().map(|(p, s)| (p.as_str(), s.as_str())).collect();
+        let (_dir, state) = project(&files);
+        match resolve_export(&state, "src/a0.ts", "leaf") {
+            ExportResolution::Defined(def) => {
+                assert_eq!(def.file, format!("src/a{}.ts", LAYERS));
+                assert_eq!(def.chain.len(), LAYERS + 1);
+            }
+            other => panic!("expected Defined, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn diamond_stars_resolve_to_the_shared_declaration() {
+        let (_dir, state) = project(&[
+            ("src/index.ts", "export * from 'pkg';\nexport * from './a';\nexport * from './b';\n"),
+            ("src/a.ts", "export * from './c';\n"),
+            ("src/b.ts", "export * from 'other';\nexport * from './c';\n"),
+            ("src/c.ts", "export const shared = 1;\n"),
+        ]);
+        match resolve_export(&state, "src/index.ts", "shared") {
+            ExportResolution::Defined(def) => assert_eq!(def.file, "src/c.ts"),
+            other => panic!("expected Defined, got {:?}", other),
+        }
+        match resolve_export(&state, "src/b.ts", "shared") {
+            ExportResolution::Defined(def) => assert_eq!(def.file, "src/c.ts"),
+            other => panic!("expected Defined, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn finds_direct_aliased_barrel_and_namespace_references() {
+        let (_dir, state) = project(&[
+            ("src/utils/api.ts", "export function validateUser(u: string) { return !!u; }\nexport type User = { id: string };\n"),
+            ("src/utils/index.ts", "export { validateUser as check } from './api';\nexport * from './api';\n"),
+            ("src/direct.ts", "import { validateUser, type User } from './utils/api';\nconst u: User = { id: '' };\nvalidateUser(u.id);\n"),
+            ("src/aliased.ts", "import { check } from './utils';\n\nexport const ok = check('x') && check('y');\n"),
+            ("src/ns.tsx", "import * as api from './utils/api';\napi.validateUser('z');\n"),
+            ("src/unrelated.ts", "import { User } from './utils/api';\nlet x: User;\n"),
+        ]);
+
+        let refs = find_references(&state, "src/utils/api.ts", "validateUser", true).unwrap();
+        assert_eq!(refs.definition.file, "src/utils/api.ts");
+        let summary: Vec<(&str, &str, &str, Vec<u32>)> = refs
+            .references
+            .iter()
+            .map(|r| {
+                (
+                    r.file.as_str(),
+                    r.local.as_str(),
+                    r.kind,
+                    r.usages.iter().map(|l| l.start_line).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/aliased.ts", "check", "named", vec![3, 3]),
+                ("src/direct.ts", "validateUser", "named", vec![3]),
+                ("src/ns.tsx", "api", "namespace", vec![2]),
+                ("src/utils/index.ts", "check", "reexport", vec![]),
+                ("src/utils/index.ts", "*", "reexport", vec![]),
+            ]
+        );
+
+        let types = find_references(&state, "src/utils/index.ts", "User", true).unwrap();
+        assert_eq!(types.definition.file, "src/utils/api.ts");
+        let direct = types
+            .references
+            .iter()
+            .find(|r| r.file == "src/direct.ts")
+            .unwrap();
+        assert!(direct.type_only);
+        assert_eq!(direct.usages[0].start_line, 2);
+
+        assert!(find_references(&state, "src/utils/api.ts", "missing", false).is_err());
+    }
+
+    #[test]
+    fn symbol_importers_exclude_other_exports_and_expand_barrels() {
+        let (_dir, state) = project(&[
+            ("src/utils.ts", "export const used = 1;\nexport const other = 2;\n"),
+            ("src/index.ts", "export * from './utils';\nexport { used as renamed } from './utils';\n"),
+            ("src/a.ts", "import { used } from './utils';\n"),
+            ("src/b.ts", "import { other } from './utils';\n"),
+            ("src/c.ts", "import { renamed } from './index';\n"),
+            ("src/d.ts", "import { used } from './index';\n"),
+            ("src/e.ts", "import * as u from './utils';\n"),
+            ("src/f.ts", "import { other } from './index';\n"),
+        ]);
+        let importers = symbol_importers(&state, "src/utils.ts", "used");
+        let got: Vec<(&str, &str)> = importers.iter().map(|i| (i.file.as_str(), i.via)).collect();
+        assert_eq!(
+            got,
+            vec![
+                ("src/a.ts", "import"),
+                ("src/c.ts", "import"),
+                ("src/d.ts", "import"),
+                ("src/e.ts", "namespace"),
+                ("src/index.ts", "reexport"),
+            ]
+        );
+        // Asking through the barrel lands on the same declaration.
+        assert_eq!(symbol_importers(&state, "src/index.ts", "renamed"), importers);
+    }
+
+    #[test]
+    fn dead_exports_respect_barrels_namespaces_dynamic_imports_and_entrypoints() {
+        let (_dir, state) = project(&[
+            ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\nexport const internal = 3;\nexport const viaBarrel = 4;\nexport type Shape = { a: number };\nconst x = internal;\n"),
+            ("src/index.ts", "export * from './lib';\n"),
+            ("src/app.ts", "import { used } from './lib';\nexport function main() { return import('./lazy'); }\n"),
+            ("src/lazy.ts", "export const lazyThing = 1;\n"),
+            ("src/ns.ts", "import * as all from './nsTarget';\nexport const n = all;\n"),
+            ("src/nsTarget.ts", "export const a = 1;\n"),
+            ("src/pages/home.tsx", "export default function Home() { return null; }\n"),
+        ]);
+        let entry = crate::graph::glob_matcher(
+            &state.root,
+            &["src/pages/**".to_string(), "src/app.ts".to_string()],
+        )
+        .unwrap();
+        let dead = dead_exports(&state, &entry);
+        let got: Vec<(&str, &str, &str)> = dead
+            .iter()
+            .map(|d| (d.file.as_str(), d.name.as_str(), d.confidence))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("src/lib.ts", "unused", "medium"),
+                ("src/lib.ts", "internal", "medium"),
+                ("src/lib.ts", "viaBarrel", "medium"),
+                ("src/lib.ts", "Shape", "medium"),
+                ("src/ns.ts", "n", "low"),
+            ]
+        );
+        let internal = &dead[1];
+        assert!(internal.notes.iter().any(|n| n.contains("used inside src/lib.ts")));
+        assert!(dead[3].notes.iter().any(|n| n == "type-only export"));
+
+        // Once the barrel is declared a public entry, its forwards are live.
+        let entry = crate::graph::glob_matcher(
+            &state.root,
+            &["src/pages/**".into(), "src/app.ts".into(), "src/index.ts".into()],
+        )
+        .unwrap();
+        let names: Vec<String> = dead_exports(&state, &entry).into_iter().map(|d| d.name).collect();
+        assert_eq!(names, vec!["n"]);
+    }
+
+    #[test]
+    fn call_edges_cross_imports_barrels_and_namespaces() {
+        let (_dir, state) = project(&[
+            ("src/utils/api.ts", "export function validateUser(u: string) { return check(u); }\nfunction check(u: string) { return !!u; }\n"),
+            ("src/utils/index.ts", "export * from './api';\n"),
+            ("src/components/Form.tsx", "import { validateUser as v } from '../utils';\nexport function Form() {\n  return v('a') ? submit() : null;\n}\nfunction submit() { return null; }\n"),
+            ("src/app.ts", "import * as api from './utils/api';\nimport { Form } from './components/Form';\nexport function main() {\n  api.validateUser('x');\n  Form();\n}\n"),
+        ]);
+
+        let direct = callers(&state, "src/utils/api.ts", "validateUser", 1);
+        let got: Vec<(&str, &str, bool)> = direct
+            .iter()
+            .map(|c| (c.file.as_str(), c.node.as_str(), c.cross_file))
+            .collect();
+        assert_eq!(
+            got,
+            vec![("src/app.ts", "main", true), ("src/components/Form.tsx", "Form", true)]
+        );
+        assert_eq!(direct[1].lines, vec![3]);
+
+        // `check` is only reachable from other files transitively.
+        let deep = callers(&state, "src/utils/api.ts", "check", 3);
+        let got: Vec<(&str, &str, usize)> = deep
+            .iter()
+            .map(|c| (c.file.as_str(), c.node.as_str(), c.depth))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("src/utils/api.ts", "validateUser", 1),
+                ("src/app.ts", "main", 2),
+                ("src/components/Form.tsx", "Form", 2),
+            ]
+        );
+
+        let down = callees(&state, "src/app.ts", "main", 2);
+        let got: Vec<(&str, &str, usize)> = down
+            .iter()
+            .map(|c| (c.file.as_str(), c.node.as_str(), c.depth))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("src/components/Form.tsx", "Form", 1),
+                ("src/utils/api.ts", "validateUser", 1),
+                ("src/components/Form.tsx", "submit", 2),
+                ("src/utils/api.ts", "check", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn type_usages_follow_imports_aliases_and_barrels() {
+        let (_dir, state) = project(&[
+            ("src/types.ts", "export interface UserData { id: string }\nexport type List = UserData[];\nfunction internal(u: UserData) {}\n"),
+            ("src/index.ts", "export * from './types';\n"),
+            ("src/api.ts", "import type { UserData as U } from './index';\nexport function get(): Promise<U> { return null as any; }\nfunction helper(u: U) {}\n"),
+            ("src/models.ts", "import * as t from './types';\nexport class Store { private cache: t.UserData; save(u: t.UserData): void {} }\n"),
+            ("src/other.ts", "import type { List } from './types';\nexport const l: List = [];\n"),
+        ]);
+        let found = find_type_usages(&state, "src/index.ts", "UserData", false).unwrap();
+        assert_eq!(found.definition.file, "src/types.ts");
+        let got: Vec<(&str, &str, &str)> = found
+            .usages
+            .iter()
+            .map(|u| (u.file.as_str(), u.symbol.as_str(), u.written_as.as_str()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                ("src/api.ts", "get", "U"),
+                ("src/models.ts", "Store.save", "t.UserData"),
+                ("src/types.ts", "List", "UserData"),
+            ]
+        );
+
+        let all = find_type_usages(&state, "src/types.ts", "UserData", true).unwrap();
+        let symbols: Vec<&str> = all.usages.iter().map(|u| u.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["get", "helper", "Store.save", "List", "internal"]);
+    }
+
+    #[test]
+    fn component_props_imported_from_another_file_are_resolved() {
+        let (_dir, state) = project(&[
+            ("src/props.ts", "export interface CardProps extends Base { title: string; subtitle?: string }\n"),
+            ("src/index.ts", "export * from './props';\n"),
+            ("src/Card.tsx", "import type { CardProps as P } from './index';\nexport const Card = ({ title }: P) => <h1>{title}</h1>;\n"),
+        ]);
+        let found = components(&state, Some("src/Card.tsx"));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file, "src/Card.tsx");
+        let props = found[0].component.props.as_ref().unwrap();
+        assert_eq!(props.type_name.as_deref(), Some("P"));
+        assert_eq!(props.defined_in.as_deref(), Some("src/props.ts"));
+        let names: Vec<&str> = props.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["title", "subtitle"]);
+        assert_eq!(props.extends, ["Base"]);
+        assert_eq!(components(&state, None).len(), 1);
+    }
+}
@@ -0,0 +1,266 @@
+//! Named types referenced by each symbol's signature.
+//!
+//! Collects `TSTypeReference` names from parameter and return types, type
+//! parameter constraints, `extends`/`implements` clauses and property types,
+//! keyed by symbol name (`Class.method` for methods, as in `symbols`).
+//! Function bodies are never entered. Names are recorded as written
+//! (`User`, `ns.User`) and resolved to their declaring file at query time.
+
+use oxc_ast::ast::*;
+use oxc_ast_visit::Visit;
+use oxc_syntax::scope::ScopeFlags;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::calls::is_function_like;
+use crate::skeleton::member_name;
+
+#[derive(Default)]
+struct TypeRefs {
+    refs: BTreeSet<String>,
+    /// Type parameters declared inside the visited signature; references to
+    /// them are not type dependencies.
+    params: HashSet<String>,
+}
+
+fn type_name(name: &TSTypeName<'_>) -> Option<String> {
+    match name {
+        TSTypeName::IdentifierReference(id) => Some(id.name.to_string()),
+        TSTypeName::QualifiedName(q) => {
+            type_name(&q.left).map(|left| format!("{}.{}", left, q.right.name))
+        }
+        _ => None,
+    }
+}
+
+fn expression_name(expr: &Expression<'_>) -> Option<String> {
+    match expr.get_inner_expression() {
+        Expression::Identifier(id) => Some(id.name.to_string()),
+        Expression::StaticMemberExpression(m) => {
+            expression_name(&m.object).map(|object| format!("{}.{}", object, m.property.name))
+        }
+        _ => None,
+    }
+}
+
+impl<'a> Visit<'a> for TypeRefs {
+    fn visit_ts_type_reference(&mut self, it: &TSTypeReference<'a>) {
+        if let Some(name) = type_name(&it.type_name) {
+            self.refs.insert(name);
+        }
+        if let Some(args) = &it.type_arguments {
+            self.visit_ts_type_parameter_instantiation(args);
+        }
+    }
+
+    fn visit_ts_type_parameter(&mut self, it: &TSTypeParameter<'a>) {
+        self.params.insert(it.name.name.to_string());
+        if let Some(c) = &it.constraint {
+            self.visit_ts_type(c);
+        }
+        if let Some(d) = &it.default {
+            self.visit_ts_type(d);
+        }
+    }
+
+    fn visit_ts_interface_heritage(&mut self, it: &TSInterfaceHeritage<'a>) {
+        if let Some(name) = expression_name(&it.expression) {
+            self.refs.insert(name);
+        }
+        if let Some(args) = &it.type_arguments {
+            self.visit_ts_type_parameter_instantiation(args);
+        }
+    }
+
+    fn visit_ts_class_implements(&mut self, it: &TSClassImplements<'a>) {
+        if let Some(name) = type_name(&it.expression) {
+            self.refs.insert(name);
+        }
+        if let Some(args) = &it.type_arguments {
+            self.visit_ts_type_parameter_instantiation(args);
+        }
+    }
+
+    // Signatures only.
+    fn visit_function_body(&mut self, _it: &FunctionBody<'a>) {}
+}
+
+impl TypeRefs {
+    fn finish(self) -> BTreeSet<String> {
+        let params = self.params;
+        self.refs
+            .into_iter()
+            .filter(|r| !params.contains(r))
+            .collect()
+    }
+}
+
+/// Heritage and non-private property types of a class. Methods are recorded
+/// as their own symbols.
+fn class_refs<'a>(class: &Class<'a>, out: &mut BTreeMap<String, BTreeSet<String>>, name: &str) {
+    let mut refs = TypeRefs::default();
+    if let Some(params) = &class.type_parameters {
+        refs.visit_ts_type_parameter_declaration(params);
+    }
+    if let Some(sup) = &class.super_class
+        && let Some(n) = expression_name(sup)
+    {
+        refs.refs.insert(n);
+    }
+    if let Some(args) = &class.super_type_arguments {
+        refs.visit_ts_type_parameter_instantiation(args);
+    }
+    for imp in &class.implements {
+        refs.visit_ts_class_implements(imp);
+    }
+    for el in &class.body.body {
+        match el {
+            ClassElement::PropertyDefinition(p) => {
+                let private = matches!(p.key, PropertyKey::PrivateIdentifier(_))
+                    || p.accessibility == Some(TSAccessibility::Private);
+                if !private && let Some(t) = &p.type_annotation {
+                    refs.visit_ts_type_annotation(t);
+                }
+            }
+            ClassElement::TSIndexSignature(s) => refs.visit_ts_index_signature(s),
+            ClassElement::MethodDefinition(m) => {
+                let Some(member) = member_name(&m.key) else {
+                    continue;
+                };
+                let mut method = TypeRefs::default();
+                // Class-level type parameters are in scope for methods.
+                method.params.extend(refs.params.iter().cloned());
+                method.visit_function(&m.value, ScopeFlags::Function);
+                out.entry(format!("{}.{}", name, member))
+                    .or_default()
+                    .extend(method.finish());
+            }
+            _ => {}
+        }
+    }
+    out.entry(name.to_string()).or_default().extend(refs.finish());
+}
+
+fn declaration_refs<'a>(decl: &Declaration<'a>, out: &mut BTreeMap<String, BTreeSet<String>>) {
+    match decl {
+        Declaration::FunctionDeclaration(f) => {
+            if let Some(id) = &f.id {
+                let mut refs = TypeRefs::default();
+                refs.visit_function(f, ScopeFlags::Function);
+                out.entry(id.name.to_string()).or_default().extend(refs.finish());
+            }
+        }
+        Declaration::ClassDeclaration(c) => {
+            if let Some(id) = &c.id {
+                class_refs(c, out, &id.name);
+            }
+        }
+        Declaration::VariableDeclaration(v) => {
+            for d in &v.declarations {
+                let Some(name) = d.id.get_identifier_name() else {
+                    continue;
+                };
+                let mut refs = TypeRefs::default();
+                if let Some(t) = &d.type_annotation {
+                    refs.visit_ts_type_annotation(t);
+                }
+                if let Some(init) = &d.init
+                    && is_function_like(init)
+                {
+                    refs.visit_expression(init);
+                }
+                out.entry(name.to_string()).or_default().extend(refs.finish());
+            }
+        }
+        Declaration::TSInterfaceDeclaration(i) => {
+            let mut refs = TypeRefs::default();
+            refs.visit_ts_interface_declaration(i);
+            out.entry(i.id.name.to_string()).or_default().extend(refs.finish());
+        }
+        Declaration::TSTypeAliasDeclaration(t) => {
+            let mut refs = TypeRefs::default();
+            refs.visit_ts_type_alias_declaration(t);
+            out.entry(t.id.name.to_string()).or_default().extend(refs.finish());
+        }
+        _ => {}
+    }
+}
+
+/// Type names referenced by each top-level symbol's signature.
+pub fn signature_type_refs(program: &Program<'_>) -> BTreeMap<String, BTreeSet<String>> {
+    let mut out = BTreeMap::new();
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(e) => {
+                if let Some(d) = &e.declaration {
+                    declaration_refs(d, &mut out);
+                }
+            }
+            Statement::ExportDefaultDeclaration(e) => match &e.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(f) => {
+                    let name = f.id.as_ref().map_or("default".to_string(), |id| id.name.to_string());
+                    let mut refs = TypeRefs::default();
+                    refs.visit_function(f, ScopeFlags::Function);
+                    out.entry(name).or_default().extend(refs.finish());
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(c) => {
+                    let name = c.id.as_ref().map_or("default", |id| id.name.as_str());
+                    class_refs(c, &mut out, name);
+                }
+                _ => {}
+            },
+            _ => {
+                if let Some(d) = stmt.as_declaration() {
+                    declaration_refs(d, &mut out);
+                }
+            }
+        }
+    }
+    out.retain(|_, refs| !refs.is_empty());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::skeleton::skeletonize_source;
+    use std::path::Path;
+
+    fn refs(src: &str, name: &str) -> Vec<String> {
+        skeletonize_source(src, Path::new("x.ts"))
+            .unwrap()
+            .symbols
+            .into_iter()
+            .find(|s| s.name == name)
+            .map(|s| s.type_refs)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn collects_signature_types_but_not_bodies_or_type_parameters() {
+        let src = "\
+import type { User, Role } from './types';
+import * as m from './models';
+export function load<T extends Base>(id: UserId, opts?: Partial<Options>): Promise<Result<T>> {
+  const x: Hidden = null as any;
+  return x;
+}
+export interface Admin extends User, m.Audited { roles: Role[]; meta: Record<string, Meta> }
+export type Pair<K> = [K, m.Model];
+export class Repo<E> extends BaseRepo<E> implements Store<User> {
+  items: Item[] = [];
+  private cache: Cache;
+  constructor(private readonly db: Db) { super(); }
+  find(q: Query<E>): E | undefined { return undefined; }
+}
+export const handler = (e: Event): Reply => { const y: Hidden2 = 1; return y; };
+export const plain: Config = { a: 1 };
+";
+        assert_eq!(refs(src, "load"), ["Base", "Options", "Partial", "Promise", "Result", "UserId"]);
+        assert_eq!(refs(src, "Admin"), ["Meta", "Record", "Role", "User", "m.Audited"]);
+        assert_eq!(refs(src, "Pair"), ["m.Model"]);
+        assert_eq!(refs(src, "Repo"), ["BaseRepo", "Item", "Store", "User"]);
+        assert_eq!(refs(src, "Repo.constructor"), ["Db"]);
+        assert_eq!(refs(src, "Repo.find"), ["Query"]);
+        assert_eq!(refs(src, "handler"), ["Event", "Reply"]);
+        assert_eq!(refs(src, "plain"), ["Config"]);
+    }
+}
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use notify::{EventKind, RecursiveMode, Watcher};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -8,11 +8,23 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
-use crate::graph::{is_skeleton_target, AppState};
-use crate::skeleton::skeletonize_file;
+use crate::architecture::CONFIG_FILE;
+use crate::graph::{is_skeleton_target, walk_targets, AppState};
+use crate::resolve::is_resolver_config;
+use crate::skeleton::{skeletonize_file, FileSkeleton};
 
 const DEBOUNCE: Duration = Duration::from_millis(200);
 
+/// Pending paths at or above this count are treated as an event storm (branch
+/// switch, codemod): flushed together once the whole storm has gone quiet for
+/// `DEBOUNCE`, parsed in parallel and applied to the graph as one batch.
+const BURST_THRESHOLD: usize = 64;
+
+/// Longest a pending change waits for the events around it to go quiet. A
+/// continuous storm (a build tool rewriting files in a loop) would otherwise
+/// push the flush back forever.
+const MAX_WAIT: Duration = Duration::from_secs(2);
+
 /// One coalesced batch of graph mutations, sent to the main loop so it can
 /// emit MCP notifications.
 #[derive(Debug, Default)]
@@ -24,14 +36,28 @@ pub struct ChangeSet {
     /// Keys removed from the graph.
     pub removed: Vec<String>,
     /// Force a list_changed push even with no added/removed keys (used to
-    /// announce initial-sweep completion, including on empty projects).
+    /// announce initial-sweep completion, including on empty projects, and
+    /// graph-wide re-resolution after a config change).
     pub force_list_changed: bool,
+    /// The architecture rules file changed (or may have): re-check every
+    /// file rather than just the keys above.
+    pub rules_changed: bool,
 }
 
 impl ChangeSet {
     pub fn is_empty(&self) -> bool {
         self.updated.is_empty() && self.added.is_empty() && self.removed.is_empty()
     }
+
+    /// Fold in keys recomputed outside the parse path (re-resolution),
+    /// skipping ones already reported.
+    fn merge_updated(&mut self, keys: Vec<String>) {
+        for key in keys {
+            if !self.added.contains(&key) && !self.updated.contains(&key) {
+                self.updated.push(key);
+            }
+        }
+    }
 }
 
 pub async fn watch_filesystem(state: Arc<AppState>, tx: mpsc::Sender<ChangeSet>) -> Result<()> {
@@ -47,9 +73,35 @@ pub async fn watch_filesystem(state: Arc<AppState>, tx: mpsc::Sender<ChangeSet>)
 
     // Coalesce events per path over a debounce window before re-parsing.
     let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    let mut last_event = Instant::now();
+    // Set when notify reports dropped events (kernel queue overflow); the
+    // only safe recovery is to rescan the whole tree.
+    let mut rescan = false;
+    // Set when a resolver config file (tsconfig, package.json) changed; every
+    // node's imports are re-resolved at the next flush.
+    let mut config_dirty = false;
+    // Set when the architecture rules file changed; every file is re-checked
+    // at the next flush.
+    let rules_path = state.root.join(CONFIG_FILE);
+    let mut rules_dirty = false;
+    // When the oldest unflushed change arrived; caps the debounce at
+    // `MAX_WAIT`.
+    let mut first_pending: Option<Instant> = None;
 
     loop {
-        let next_deadline = pending.values().min().map(|t| *t + DEBOUNCE);
+        if rescan || config_dirty || rules_dirty || !pending.is_empty() {
+            first_pending.get_or_insert_with(Instant::now);
+        } else {
+            first_pending = None;
+        }
+        let next_deadline =
+            if rescan || config_dirty || rules_dirty || pending.len() >= BURST_THRESHOLD {
+                Some(last_event + DEBOUNCE)
+            } else {
+                pending.values().min().map(|t| *t + DEBOUNCE)
+            };
+        let next_deadline =
+            next_deadline.map(|d| first_pending.map_or(d, |t| d.min(t + MAX_WAIT)));
 
         tokio::select! {
             maybe_event = watch_rx.recv() => {
@@ -57,21 +109,74 @@ pub async fn watch_filesystem(state: Arc<AppState>, tx: mpsc::Sender<ChangeSet>)
                 if !state.is_running.load(Ordering::SeqCst) {
                     continue;
                 }
+                if event.need_rescan() {
+                    tracing::warn!("watcher: notify queue overflowed, scheduling full rescan");
+                    rescan = true;
+                    last_event = Instant::now();
+                    continue;
+                }
                 if matches!(event.kind, EventKind::Access(_)) {
                     continue;
                 }
                 for path in event.paths {
+                    if path == rules_path {
+                        last_event = Instant::now();
+                        rules_dirty = true;
+                        continue;
+                    }
                     // Create/Modify/Remove/Rename all funnel through the same
                     // pending set; at flush time the filesystem is the source
                     // of truth (file exists -> upsert, gone -> remove).
                     if is_skeleton_target(&path) && !state.is_ignored(&path) {
-                        pending.insert(path, Instant::now());
+                        last_event = Instant::now();
+                        pending.insert(path, last_event);
+                    } else if is_resolver_config(&path) && !state.is_ignored(&path) {
+                        last_event = Instant::now();
+                        config_dirty = true;
+                    } else if matches!(event.kind, EventKind::Create(_))
+                        && path.is_dir()
+                        && !state.is_ignored(&path)
+                    {
+                        // Files written into a new directory before its watch
+                        // is registered produce no events of their own.
+                        last_event = Instant::now();
+                        for (_, file) in walk_targets(&state, &path) {
+                            pending.insert(file, last_event);
+                        }
                     }
                 }
             }
 
             _ = tokio::time::sleep_until(next_deadline.unwrap_or_else(Instant::now)),
               if next_deadline.is_some() => {
+                if rescan {
+                    rescan = false;
+                    rules_dirty = false;
+                    pending.clear();
+                    let changes = full_rescan(&state).await;
+                    let _ = tx.send(changes).await;
+                    continue;
+                }
+
+                let overdue = first_pending.is_some_and(|t| t.elapsed() >= MAX_WAIT);
+                if pending.len() >= BURST_THRESHOLD || config_dirty || rules_dirty || overdue {
+                    let burst: Vec<PathBuf> = pending.drain().map(|(p, _)| p).collect();
+                    if burst.len() >= BURST_THRESHOLD {
+                        tracing::info!("watcher: applying burst of {} paths as one batch", burst.len());
+                    }
+                    let mut changes = apply_burst(&state, burst).await;
+                    if std::mem::take(&mut config_dirty) {
+                        tracing::info!("watcher: resolver config changed, re-resolving graph");
+                        changes.merge_updated(reresolve(&state).await);
+                        changes.force_list_changed = true;
+                    }
+                    changes.rules_changed = std::mem::take(&mut rules_dirty);
+                    if !changes.is_empty() || changes.force_list_changed || changes.rules_changed {
+                        let _ = tx.send(changes).await;
+                    }
+                    continue;
+                }
+
                 let now = Instant::now();
                 let due: Vec<PathBuf> = pending
                     .iter()
@@ -129,3 +234,117 @@ pub async fn watch_filesystem(state: Arc<AppState>, tx: mpsc::Sender<ChangeSet>)
 
     Ok(())
 }
+
+/// Skeletonize `items` across all available cores. Order is not preserved.
+fn parse_parallel(items: Vec<(String, PathBuf)>) -> Vec<(String, Result<FileSkeleton>)> {
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let workers = std::thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(items.len());
+    let chunk = items.len().div_ceil(workers);
+    std::thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(chunk)
+            .map(|part| {
+                s.spawn(move || {
+                    part.iter()
+                        .map(|(key, path)| (key.clone(), skeletonize_file(path)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Apply parse results and removals to the graph in one atomic batch. Files
+/// that fail to parse keep their previous node, as in the per-path path.
+fn apply_parsed(
+    state: &AppState,
+    parsed: Vec<(String, Result<FileSkeleton>)>,
+    removals: Vec<String>,
+) -> ChangeSet {
+    let mut upserts = Vec::with_capacity(parsed.len());
+    for (key, res) in parsed {
+        match res {
+            Ok(ir) => upserts.push((key, ir)),
+            Err(e) => tracing::warn!("watcher: keeping previous node for {}: {}", key, e),
+        }
+    }
+    let (added, updated, removed) = state.apply_batch(upserts, removals);
+    ChangeSet {
+        updated,
+        added,
+        removed,
+        ..ChangeSet::default()
+    }
+}
+
+/// Re-parse a storm of changed paths in parallel and apply them as one batch.
+async fn apply_burst(state: &Arc<AppState>, paths: Vec<PathBuf>) -> ChangeSet {
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut existing = Vec::new();
+        let mut gone = Vec::new();
+        for path in paths {
+            let Some(key) = state.key_for(&path.to_string_lossy()) else {
+                continue;
+            };
+            if path.is_file() {
+                existing.push((key, path));
+            } else {
+                gone.push(key);
+            }
+        }
+        apply_parsed(&state, parse_parallel(existing), gone)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        tracing::error!("watcher: burst task panicked: {}", e);
+        ChangeSet::default()
+    })
+}
+
+/// Re-resolve every node's imports off the reactor (resolution stats files).
+async fn reresolve(state: &Arc<AppState>) -> Vec<String> {
+    let state = state.clone();
+    tokio::task::spawn_blocking(move || state.reresolve_all())
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("watcher: re-resolution task panicked: {}", e);
+            Vec::new()
+        })
+}
+
+/// Rebuild the graph from a fresh walk after notify dropped events: every
+/// tracked file is re-parsed and nodes whose file no longer exists are removed.
+/// Config changes may have been among the dropped events, so resolver caches
+/// are invalidated first.
+async fn full_rescan(state: &Arc<AppState>) -> ChangeSet {
+    let state = state.clone();
+    let mut changes = tokio::task::spawn_blocking(move || {
+        state.resolver.invalidate();
+        let targets = walk_targets(&state, &state.root);
+        let live: HashSet<&str> = targets.iter().map(|(k, _)| k.as_str()).collect();
+        let stale: Vec<String> = state
+            .skeleton_graph
+            .iter()
+            .map(|e| e.key().clone())
+            .filter(|k| !live.contains(k.as_str()))
+            .collect();
+        apply_parsed(&state, parse_parallel(targets), stale)
+    })
+    .await
+    .unwrap_or_else(|e| {
+        tracing::error!("watcher: rescan task panicked: {}", e);
+        ChangeSet::default()
+    });
+    changes.force_list_changed = true;
+    changes.rules_changed = true;
+    changes
+}
@@ -70,10 +70,10 @@ impl Server {
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
             for line in BufReader::new(stdout).lines().map_while(Result::ok) {
-                if let Ok(v) = serde_json::from_str::<Value>(&line) {
-                    if tx.send(v).is_err() {
-                        break;
-                    }
+                if let Ok(v) = serde_json::from_str::<Value>(&line)
+                    && tx.send(v).is_err()
+                {
+                    break;
                 }
             }
         });
@@ -130,10 +130,10 @@ impl Server {
         self.send_raw(&msg.to_string());
         let deadline = Instant::now() + Duration::from_secs(20);
         while Instant::now() < deadline {
-            if let Some(v) = self.recv(Duration::from_millis(200)) {
-                if v.get("id").and_then(|i| i.as_u64()) == Some(id) {
-                    return v;
-                }
+            if let Some(v) = self.recv(Duration::from_millis(200))
+                && v.get("id").and_then(|i| i.as_u64()) == Some(id)
+            {
+                return v;
             }
         }
         panic!("timed out waiting for response to {}", method);
@@ -275,6 +275,16 @@ fn graph_lifecycle_watcher_and_subscriptions() {
     let msgs = server.drain(Duration::from_secs(1));
     assert!(msgs.is_empty(), "node_modules event leaked: {:?}", msgs);
     assert_eq!(server.graph_keys(), expected);
+
+    // resolver config changes re-resolve the graph and announce it
+    std::fs::write(root.join("tsconfig.json"), "{\"compilerOptions\": {}}\n").unwrap();
+    let msgs = server.drain(Duration::from_secs(2));
+    assert!(
+        msgs.iter().any(|m| m["method"] == "notifications/resources/list_changed"),
+        "expected list_changed after tsconfig change, got {:?}",
+        msgs
+    );
+    assert_eq!(server.graph_keys(), expected);
 }
 
 #[test]
@@ -292,6 +302,8 @@ fn tools_cover_symbols_implementation_and_dependencies() {
     let form = find("Form").expect("Form missing");
     assert_eq!(form["kind"], "component");
     assert_eq!(form["exported"], true);
+    assert_eq!(form["location"]["start_line"], 10);
+    assert_eq!(form["location"]["end_line"], 13);
     assert_eq!(find("FormProps").unwrap()["kind"], "interface");
     assert_eq!(find("UserData").unwrap()["kind"], "type");
 
@@ -299,9 +311,12 @@ fn tools_cover_symbols_implementation_and_dependencies() {
     let res = server.call_tool("search_symbols", json!({"query": "validate"}));
     let hits: Vec<Value> =
         serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
-    assert!(hits
+    let hit = hits
         .iter()
-        .any(|h| h["file"] == "src/utils/api.ts" && h["name"] == "validateUser"));
+        .find(|h| h["file"] == "src/utils/api.ts" && h["name"] == "validateUser")
+        .expect("validateUser hit");
+    assert_eq!(hit["location"]["start"], 0);
+    assert_eq!(hit["location"]["end"], VALIDATE_FN.len());
 
     // get_implementation returns the exact original slice
     let res = server.call_tool(
@@ -341,6 +356,16 @@ fn tools_cover_symbols_implementation_and_dependencies() {
     assert_eq!(deps["imports"], json!(["src/utils/api.ts"]));
     assert_eq!(deps["external"], json!(["react"]));
 
+    // metrics: Form.tsx is the entrypoint, api.ts one import below it
+    let res = server.call_tool("graph_metrics", json!({"limit": 1}));
+    let metrics: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!((metrics["entrypoints"].as_u64(), metrics["total"].as_u64()), (Some(1), Some(2)));
+    let top = &metrics["rows"][0];
+    assert_eq!(top["name"], "src/utils/api.ts");
+    assert_eq!((top["fan_in"].as_u64(), top["instability"].as_f64(), top["depth"].as_u64()), (Some(1), Some(0.0), Some(1)));
+    let res = server.call_tool("graph_metrics", json!({"sort_by": "size"}));
+    assert_eq!(res["isError"], true);
+
     // tool-level failure is isError, not a JSON-RPC error
     let res = server.call_tool("list_symbols", json!({"file_path": "src/missing.ts"}));
     assert_eq!(res["isError"], true);
@@ -388,3 +413,328 @@ fn resource_uris_are_percent_encoded_and_decoded() {
     let text = res["result"]["contents"][0]["text"].as_str().unwrap();
     assert!(text.contains("x = 1"));
 }
+
+#[test]
+fn routes_resource_and_tool_list_file_and_code_routes() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    std::fs::create_dir_all(root.join("src/pages/users")).unwrap();
+    std::fs::write(
+        root.join("src/pages/users/[id].tsx"),
+        "export default function User() { return null; }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        root.join("src/server.ts"),
+        "import express from 'express';\nconst app = express();\napp.get('/health', health);\n",
+    )
+    .unwrap();
+    let mut server = Server::start(&root);
+
+    let res = server.request("resources/read", json!({"uri": "skeleton://project/routes"}));
+    let routes: Value =
+        serde_json::from_str(res["result"]["contents"][0]["text"].as_str().unwrap()).unwrap();
+    let got: Vec<(&str, &str, &str)> = routes
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| {
+            (
+                r["path"].as_str().unwrap(),
+                r["framework"].as_str().unwrap(),
+                r["file"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        got,
+        vec![
+            ("/health", "express", "src/server.ts"),
+            ("/users/[id]", "next-pages", "src/pages/users/[id].tsx"),
+        ]
+    );
+
+    let res = server.call_tool("list_routes", json!({"framework": "express"}));
+    let routes: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(routes[0]["method"], "GET");
+    assert_eq!(routes[0]["handler"], "health");
+    assert_eq!(routes.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn architecture_rules_are_rechecked_as_files_and_rules_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    write_fixture(&root);
+    std::fs::write(
+        root.join(".skeletonizer.json"),
+        r#"{ "architecture": { "rules": [ { "name": "ui-no-utils", "from": ["src/components/"], "disallow": ["src/utils/"] } ] } }"#,
+    )
+    .unwrap();
+    let mut server = Server::start(&root);
+    let check = |server: &mut Server, args: Value| -> Value {
+        let res = server.call_tool("check_architecture", args);
+        serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap()
+    };
+
+    let report = check(&mut server, json!({}));
+    assert_eq!(report["rules"], 1);
+    let violations = report["violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 1, "{:?}", violations);
+    assert_eq!(violations[0]["file"], "src/components/Form.tsx");
+    assert_eq!(violations[0]["target"], "src/utils/api.ts");
+    assert_eq!(violations[0]["source"], "../utils/api");
+    assert_eq!(violations[0]["location"]["start_line"], 2);
+    assert_eq!(check(&mut server, json!({"rule": "other"}))["violations"], json!([]));
+
+    // a new offending file is checked on its watcher batch
+    std::fs::write(root.join("src/components/List.tsx"), "import { UserService } from '../utils/api';
+").unwrap();
+    server.drain(Duration::from_secs(2));
+    let report = check(&mut server, json!({"file_path": "src/components/List.tsx"}));
+    assert_eq!(report["violations"].as_array().unwrap().len(), 1);
+
+    // editing the rules re-checks everything; a broken config is reported
+    std::fs::write(root.join(".skeletonizer.json"), r#"{ "architecture": { "rules": [ { "name": "bad" } ] } }"#).unwrap();
+    server.drain(Duration::from_secs(2));
+    let report = check(&mut server, json!({}));
+    assert_eq!(report["violations"], json!([]));
+    assert!(report["error"].as_str().unwrap().contains("bad"));
+}
+
+/// Run a one-shot subcommand; returns (exit code, stdout, stderr).
+fn run_cli(root: &Path, args: &[&str]) -> (i32, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_semantic_skeletonizer"))
+        .arg("--root")
+        .arg(root)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn cli_subcommands_print_and_exit() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    write_fixture(&root);
+
+    let (code, stdout, _) = run_cli(&root, &["dump"]);
+    assert_eq!(code, 0);
+    let graph: Value = serde_json::from_str(&stdout).unwrap();
+    let mut keys: Vec<&String> = graph.as_object().unwrap().keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["src/components/Form.tsx", "src/utils/api.ts"]);
+
+    let (code, stdout, _) = run_cli(&root, &["dump", "./src/utils/api.ts"]);
+    assert_eq!(code, 0);
+    assert!(serde_json::from_str::<Value>(&stdout).unwrap()["symbols"].is_array());
+
+    let (code, stdout, _) = run_cli(&root, &["symbols", "src/components/Form.tsx"]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("\"name\":\"Form\""));
+
+    let (code, stdout, _) = run_cli(&root, &["deps", "src/utils/api.ts"]);
+    assert_eq!(code, 0);
+    let deps: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(deps["imported_by"], json!(["src/components/Form.tsx"]));
+
+    let (code, stdout, _) = run_cli(&root, &["impl", "src/utils/api.ts", "validateUser"]);
+    assert_eq!((code, stdout.trim_end()), (0, VALIDATE_FN));
+
+    let (code, stdout, stderr) = run_cli(&root, &["impl", "src/utils/api.ts", "nosuch"]);
+    assert_eq!(code, 1);
+    assert!(stdout.is_empty() && stderr.contains("validateUser"));
+    assert_eq!(run_cli(&root, &["dump", "src/missing.ts"]).0, 1);
+    let (code, _, stderr) = run_cli(&root, &["frobnicate"]);
+    assert_eq!(code, 2);
+    assert!(stderr.contains("usage:"));
+}
+
+#[test]
+fn cli_check_writes_sarif_and_honours_a_baseline() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    write_fixture(&root);
+    let (code, stdout, _) = run_cli(&root, &["check"]);
+    assert_eq!((code, stdout.as_str()), (0, "0 findings\n"));
+
+    std::fs::write(root.join("src/utils/cycle.ts"), "import { Form } from '../components/Form';\n").unwrap();
+    std::fs::write(
+        root.join("src/utils/api.ts"),
+        format!("import {{ x }} from './cycle';\nimport './api.css';\nimport {{ y }} from './missing';\n{}\n", VALIDATE_FN),
+    )
+    .unwrap();
+    std::fs::write(root.join("src/utils/api.css"), "").unwrap();
+    let sarif = root.join("check.sarif");
+    let (code, stdout, _) = run_cli(&root, &["check", "--sarif", sarif.to_str().unwrap()]);
+    assert_eq!(code, 1);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "src/components/Form.tsx:2:1: import-cycle: import cycle between src/components/Form.tsx, src/utils/api.ts, src/utils/cycle.ts",
+            "src/utils/api.ts:3:1: unresolved-import: cannot resolve './missing'",
+            "2 findings",
+        ]
+    );
+    let log: Value = serde_json::from_str(&std::fs::read_to_string(&sarif).unwrap()).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results[1]["ruleId"], "unresolved-import");
+    assert_eq!(results[1]["locations"][0]["physicalLocation"]["region"]["startLine"], 3);
+
+    let baseline = root.join("baseline.json");
+    let baseline = baseline.to_str().unwrap();
+    assert_eq!(run_cli(&root, &["check", "--baseline", baseline, "--write-baseline"]).0, 0);
+    let (code, stdout, _) = run_cli(&root, &["check", "--baseline", baseline]);
+    assert_eq!((code, stdout.as_str()), (0, "0 findings (2 suppressed by baseline)\n"));
+
+    std::fs::write(root.join("src/utils/broken.ts"), "export const ok = 1;\nexport function (\n").unwrap();
+    let (code, stdout, _) = run_cli(&root, &["check", "--baseline", baseline]);
+    assert_eq!(code, 1);
+    assert!(stdout.starts_with("src/utils/broken.ts:2:"), "{}", stdout);
+    assert!(stdout.ends_with("1 finding (2 suppressed by baseline)\n"));
+    assert_eq!(run_cli(&root, &["check", "--baseline", "/nonexistent/b.json"]).0, 2);
+}
+
+#[test]
+fn mass_rewrite_is_applied_as_one_coalesced_batch() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    write_fixture(&root);
+    let mut server = Server::start(&root);
+
+    // A codemod-sized burst: well above the watcher's storm threshold.
+    std::fs::create_dir_all(root.join("src/gen")).unwrap();
+    for i in 0..150 {
+        std::fs::write(
+            root.join(format!("src/gen/m{}.ts", i)),
+            format!("export const v{} = {};\n", i, i),
+        )
+        .unwrap();
+    }
+
+    let msgs = server.drain(Duration::from_secs(3));
+    let list_changed = msgs
+        .iter()
+        .filter(|m| m["method"] == "notifications/resources/list_changed")
+        .count();
+    assert_eq!(list_changed, 1, "burst must be coalesced, got {:?}", msgs);
+    let keys = server.graph_keys();
+    assert_eq!(keys.iter().filter(|k| k.starts_with("src/gen/")).count(), 150);
+
+    // Removing the burst is coalesced the same way.
+    std::fs::remove_dir_all(root.join("src/gen")).unwrap();
+    let msgs = server.drain(Duration::from_secs(3));
+    assert!(msgs.iter().any(|m| m["method"] == "notifications/resources/list_changed"));
+    assert!(!server.graph_keys().iter().any(|k| k.starts_with("src/gen/")));
+}
+
+#[test]
+fn git_backed_tools_read_revisions_history_and_branch_changes() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = fixture_root(&dir);
+    write_fixture(&root);
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&root)
+            .args(["-c", "user.name=t", "-c", "user.email=t@t", "-c", "commit.gpgsign=false"])
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?}", args);
+    };
+    git(&["init", "-q", "-b", "main"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "base"]);
+    std::fs::write(root.join("src/utils/api.ts"), "export const checkUser = 1;\n").unwrap();
+    let mut server = Server::start(&root);
+
+    let names = |res: &Value| -> Vec<String> {
+        let symbols: Vec<Value> =
+            serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+        symbols.iter().map(|s| s["name"].as_str().unwrap().to_string()).collect()
+    };
+    let res = server.call_tool("list_symbols", json!({"file_path": "src/utils/api.ts", "rev": "HEAD"}));
+    assert_eq!(names(&res), ["validateUser", "UserService", "UserService.getUser"]);
+    let res = server.call_tool("list_functions", json!({"file_path": "src/utils/api.ts", "rev": "main"}));
+    assert_eq!(names(&res), ["validateUser", "UserService.getUser"]);
+    // the live graph still reflects the working tree
+    let res = server.call_tool("list_symbols", json!({"file_path": "src/utils/api.ts"}));
+    assert_eq!(names(&res), ["checkUser"]);
+
+    let res = server.call_tool(
+        "get_implementation",
+        json!({"file_path": "src/utils/api.ts", "target_node": "validateUser", "rev": "HEAD"}),
+    );
+    assert_eq!(res["content"][0]["text"], VALIDATE_FN);
+
+    let res = server.request(
+        "resources/read",
+        json!({"uri": "skeleton://project/file/src/utils/api.ts?rev=HEAD"}),
+    );
+    let text = res["result"]["contents"][0]["text"].as_str().unwrap();
+    assert!(text.contains("validateUser") && !text.contains("checkUser"));
+
+    let res = server.call_tool("list_symbols", json!({"file_path": "src/utils/api.ts", "rev": "nosuch"}));
+    assert_eq!(res["isError"], true);
+    let res = server.request(
+        "resources/read",
+        json!({"uri": "skeleton://project/file/src/utils/api.ts?rev=nosuch"}),
+    );
+    assert_eq!(res["error"]["code"], -32002);
+
+    // revision vs working tree: structural changes only
+    let res = server.call_tool("diff_revisions", json!({"from": "HEAD"}));
+    let diff: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(diff["to"], "working tree");
+    let files = diff["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{:?}", files);
+    assert_eq!(files[0]["file"], "src/utils/api.ts");
+    let names = |field: &str| -> Vec<&str> {
+        files[0][field].as_array().unwrap().iter().map(|s| s["name"].as_str().unwrap()).collect()
+    };
+    assert_eq!(names("added_symbols"), ["checkUser"]);
+    assert!(names("removed_symbols").contains(&"validateUser"));
+    let res = server.call_tool("diff_revisions", json!({"from": "HEAD", "to": "main"}));
+    let diff: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(diff["files"], json!([]));
+
+    // review context: the edited file, its changed symbols and its importers
+    std::fs::write(root.join("notes.md"), "wip\n").unwrap();
+    let res = server.call_tool("get_review_context", json!({}));
+    let review: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(review["base"], "main");
+    let files = review["files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{:?}", files);
+    assert_eq!(files[0]["file"], "src/utils/api.ts");
+    assert_eq!(files[0]["status"], "modified");
+    assert_eq!(files[0]["changed_symbols"], json!(["checkUser"]));
+    assert!(files[0]["skeleton"]["symbols"].is_array());
+    assert_eq!(
+        review["dependents"],
+        json!([{"file": "src/components/Form.tsx", "imports": ["src/utils/api.ts"]}])
+    );
+    assert_eq!(review["other_changes"], json!(["notes.md"]));
+    let res = server.call_tool("get_review_context", json!({"base": "nosuch"}));
+    assert_eq!(res["isError"], true);
+
+    // history: one commit touching both graph files
+    let res = server.call_tool("get_change_hotspots", json!({"min_co_changes": 1}));
+    let report: Value = serde_json::from_str(res["content"][0]["text"].as_str().unwrap()).unwrap();
+    assert_eq!(report["commits"], 1);
+    assert_eq!(report["churn"].as_array().unwrap().len(), 2);
+    assert_eq!(report["hotspots"][0]["file"], "src/utils/api.ts");
+    assert_eq!(report["hidden_coupling"], json!([]), "Form.tsx imports api.ts");
+    let res = server.call_tool("get_change_hotspots", json!({"limit": "many"}));
+    assert_eq!(res["isError"], true);
+}